@@ -0,0 +1,142 @@
+use crate::dictionary::make_alphagram;
+use crate::error::InputError;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(test)]
+mod test;
+
+// Multi-letter tiles are stored as single characters from the private use area, so that every
+// tile is one char in alphagrams, racks and the trie
+const FIRST_TILE: u32 = 0xE000;
+
+// How words and racks are turned into tiles: case folding, optional accent stripping, and tiles
+// written with more than one letter, such as Welsh "LL".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    tiles: Vec<String>,
+    strip_accents: bool,
+    // Letters left alone when stripping accents, such as Spanish "Ñ"
+    keep: Vec<char>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Alphabet {
+    pub fn new() -> Self {
+        Self {
+            tiles: vec![],
+            strip_accents: true,
+            keep: vec![],
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self, InputError> {
+        let mut alphabet = Self::new();
+        match name.to_lowercase().as_str() {
+            "english" => {}
+            "spanish" => {
+                alphabet.set_tiles(&["CH", "LL", "RR"]);
+                alphabet.keep = vec!['Ñ'];
+            }
+            // Umlauts are tiles of their own, and upper-casing turns ß into SS
+            "german" => alphabet.strip_accents = false,
+            "welsh" => alphabet.set_tiles(&["CH", "DD", "FF", "NG", "LL", "PH", "RH", "TH"]),
+            _ => return Err(InputError::UnknownAlphabet(name.to_owned())),
+        }
+        Ok(alphabet)
+    }
+
+    pub fn set_tiles(&mut self, tiles: &[&str]) {
+        self.tiles = tiles
+            .iter()
+            .map(|tile| self.normalise(tile))
+            .filter(|tile| tile.chars().count() > 1)
+            .collect();
+        // Longest first, so that the longest tile matching some text wins
+        self.tiles
+            .sort_by_key(|tile| std::cmp::Reverse(tile.chars().count()));
+    }
+
+    pub fn set_strip_accents(&mut self, strip_accents: bool) {
+        self.strip_accents = strip_accents;
+    }
+
+    // Upper-cases the text and strips accents if need be, but leaves multi-letter tiles spelt out
+    pub fn normalise(&self, text: &str) -> String {
+        let text = text.trim().to_uppercase();
+        if !self.strip_accents {
+            return text.nfc().collect();
+        }
+
+        text.nfc()
+            .flat_map(|c| {
+                let decomposed: Vec<char> = if self.keep.contains(&c) {
+                    vec![c]
+                } else {
+                    std::iter::once(c)
+                        .nfd()
+                        .filter(|&c| !is_combining_mark(c))
+                        .collect()
+                };
+                decomposed
+            })
+            .collect()
+    }
+
+    // Normalises the text and replaces each multi-letter tile with its single character
+    pub fn encode(&self, text: &str) -> String {
+        let text = self.normalise(text);
+
+        let mut encoded = String::new();
+        let mut rest = text.as_str();
+        'outer: while let Some(c) = rest.chars().next() {
+            for (i, tile) in self.tiles.iter().enumerate() {
+                if let Some(after) = rest.strip_prefix(tile.as_str()) {
+                    encoded.push(char::from_u32(FIRST_TILE + i as u32).unwrap());
+                    rest = after;
+                    continue 'outer;
+                }
+            }
+            encoded.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        encoded
+    }
+
+    pub fn decode(&self, encoded: &str) -> String {
+        encoded.chars().map(|c| self.decode_char(c)).collect()
+    }
+
+    pub fn decode_char(&self, c: char) -> String {
+        (c as u32)
+            .checked_sub(FIRST_TILE)
+            .and_then(|i| self.tiles.get(i as usize))
+            .cloned()
+            .unwrap_or_else(|| c.to_string())
+    }
+
+    // Whether the letters are just A to Z, with no tiles or accented letters
+    pub fn is_plain(&self) -> bool {
+        self.tiles.is_empty() && self.keep.is_empty() && self.strip_accents
+    }
+
+    // Whether an encoded character is one of the alphabet's letters or tiles: A to Z, any letters
+    // kept when stripping accents, and accented forms of those when accents aren't stripped
+    pub fn is_letter(&self, c: char) -> bool {
+        let is_base_letter = |c: char| c.is_ascii_uppercase() || self.keep.contains(&c);
+        is_base_letter(c)
+            || (!self.strip_accents && std::iter::once(c).nfd().next().is_some_and(is_base_letter))
+            || (c as u32)
+                .checked_sub(FIRST_TILE)
+                .is_some_and(|i| (i as usize) < self.tiles.len())
+    }
+
+    pub fn alphagram(&self, text: &str) -> String {
+        make_alphagram(&self.encode(text))
+    }
+}
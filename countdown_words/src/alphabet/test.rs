@@ -0,0 +1,78 @@
+use super::*;
+use anyhow::Result;
+
+#[test]
+fn test_english() {
+    let alphabet = Alphabet::new();
+    assert_eq!(alphabet.normalise(" café\n"), "CAFE");
+    assert_eq!(alphabet.encode("naïve"), "NAIVE");
+    assert_eq!(alphabet.alphagram("Crates"), "ACERST");
+}
+
+#[test]
+fn test_spanish() -> Result<()> {
+    let alphabet = Alphabet::parse("spanish")?;
+    assert_eq!(alphabet.normalise("Año"), "AÑO");
+    assert_eq!(alphabet.normalise("canción"), "CANCION");
+
+    // LL is one tile, so LLORAR is five tiles
+    let encoded = alphabet.encode("llorar");
+    assert_eq!(encoded.chars().count(), 5);
+    assert_eq!(alphabet.decode(&encoded), "LLORAR");
+    assert_eq!(alphabet.encode("chico").chars().count(), 4);
+
+    // Both spellings of the same letters give the same alphagram
+    assert_eq!(alphabet.alphagram("AÑO"), alphabet.alphagram("oña"));
+
+    Ok(())
+}
+
+#[test]
+fn test_german() -> Result<()> {
+    let alphabet = Alphabet::parse("german")?;
+    assert_eq!(alphabet.normalise("Straße"), "STRASSE");
+    assert_eq!(alphabet.normalise("Bär"), "BÄR");
+    // Decomposed input is treated the same as composed
+    assert_eq!(alphabet.normalise("Ba\u{0308}r"), "BÄR");
+    assert_ne!(alphabet.alphagram("BÄR"), alphabet.alphagram("BAR"));
+
+    Ok(())
+}
+
+#[test]
+fn test_welsh() -> Result<()> {
+    let alphabet = Alphabet::parse("welsh")?;
+    let encoded = alphabet.encode("Llanddŵr");
+    assert_eq!(encoded.chars().count(), 6);
+    assert_eq!(alphabet.decode(&encoded), "LLANDDWR");
+    assert_eq!(alphabet.decode_char(encoded.chars().next().unwrap()), "LL");
+
+    assert!(Alphabet::parse("klingon").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_is_letter() -> Result<()> {
+    let alphabet = Alphabet::new();
+    assert!(alphabet.is_letter('Q'));
+    for c in ['Ж', 'Λ', '中', 'Ñ', '1', '-'] {
+        assert!(!alphabet.is_letter(c), "{} is not an English letter", c);
+    }
+    assert!(matches!(
+        crate::rack::Rack::parse_in("catж", &alphabet),
+        Err(InputError::BadLetter(letter)) if letter == "Ж"
+    ));
+
+    let spanish = Alphabet::parse("spanish")?;
+    assert!(spanish.is_letter('Ñ'));
+    assert!(spanish.encode("ll").chars().all(|c| spanish.is_letter(c)));
+    assert!(!spanish.is_letter('Ж'));
+
+    // Umlauts are letters when accents are kept
+    let german = Alphabet::parse("german")?;
+    assert!(german.is_letter('Ä'));
+    assert!(!german.is_letter('Λ'));
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use crate::dictionary::{make_alphagram, Dictionary, Entry, Word};
+use crate::rack::Rack;
+use crate::scoring::Scoring;
+
+#[cfg(test)]
+mod test;
+
+// How a declared word compares with the best that could have been made from the rack
+#[derive(Debug)]
+pub struct Analysis<'a> {
+    pub word: String,
+    pub in_dictionary: bool,
+    pub formable: bool,
+    // Zero unless the word is both in the dictionary and formable
+    pub score: u32,
+    pub best_score: u32,
+    pub best: Vec<&'a Word>,
+    // Other words of the same length as the declared word
+    pub same_length: Vec<&'a Word>,
+    pub longer: Vec<&'a Word>,
+}
+
+impl<'a> Analysis<'a> {
+    pub fn is_valid(&self) -> bool {
+        self.in_dictionary && self.formable
+    }
+}
+
+pub fn analyse<'a>(
+    dictionary: &'a Dictionary,
+    rack: &Rack,
+    declared: &str,
+    scoring: &Scoring,
+    usable: &dyn Fn(&Word) -> bool,
+) -> Analysis<'a> {
+    let words = &dictionary.words;
+    let declared = dictionary.alphabet.normalise(declared);
+    let encoded = dictionary.alphabet.encode(&declared);
+    let alphagram = make_alphagram(&encoded);
+    let len = encoded.chars().count();
+
+    let in_dictionary = words.get(&alphagram).is_some_and(|entry| {
+        entry
+            .words
+            .iter()
+            .any(|word| word.text == declared && usable(word))
+    });
+    let blanks = rack.make(&encoded);
+
+    let found: Vec<_> = rack
+        .find(words)
+        .into_iter()
+        .map(|(entry, blanks)| {
+            let blanks: Vec<char> = blanks.into_iter().flatten().collect();
+            let words: Vec<&Word> = entry.words.iter().filter(|word| usable(word)).collect();
+            (scoring.score(&entry.alphagram, &blanks), entry, words)
+        })
+        .filter(|(_, _, words)| !words.is_empty())
+        .collect();
+
+    let best_score = found.iter().map(|(score, _, _)| *score).max().unwrap_or(0);
+
+    // Words other than the declared one from the entries that pass `keep`
+    let matching = |keep: &dyn Fn(u32, &Entry) -> bool| -> Vec<&'a Word> {
+        let mut matching: Vec<_> = found
+            .iter()
+            .filter(|(score, entry, _)| keep(*score, entry))
+            .flat_map(|(_, _, words)| words.iter().copied())
+            .filter(|word| word.text != declared)
+            .collect();
+        matching.sort_unstable_by_key(|word| (word.text.chars().count(), &word.text));
+        matching
+    };
+
+    Analysis {
+        score: match &blanks {
+            Some(blanks) if in_dictionary => {
+                let blanks: Vec<char> = blanks.iter().flatten().copied().collect();
+                scoring.score(&alphagram, &blanks)
+            }
+            _ => 0,
+        },
+        in_dictionary,
+        formable: blanks.is_some(),
+        best_score,
+        best: matching(&|score, _| score == best_score),
+        same_length: matching(&|_, entry| entry.num_letters() == len),
+        longer: matching(&|_, entry| entry.num_letters() > len),
+        word: declared,
+    }
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::testutil::{fixture, texts};
+
+#[test]
+fn test_analyse() {
+    let dictionary = fixture();
+    let rack = Rack::parse("CRATESX").unwrap();
+
+    let analysis = analyse(&dictionary, &rack, "trace", &Scoring::Countdown, &|_| true);
+    assert!(analysis.is_valid());
+    assert_eq!(analysis.word, "TRACE");
+    assert_eq!(analysis.score, 5);
+    assert_eq!(analysis.best_score, 6);
+    assert_eq!(texts(&analysis.best), vec!["CATERS", "CRATES", "REACTS"]);
+    assert_eq!(
+        texts(&analysis.same_length),
+        vec!["CASTE", "CRATE", "REACT"]
+    );
+    assert_eq!(analysis.longer, analysis.best);
+}
+
+#[test]
+fn test_analyse_invalid() {
+    let dictionary = fixture();
+    let rack = Rack::parse("CRATES").unwrap();
+
+    // In the dictionary, but not in the rack
+    let analysis = analyse(&dictionary, &rack, "GRANITES", &Scoring::Countdown, &|_| {
+        true
+    });
+    assert!(analysis.in_dictionary);
+    assert!(!analysis.formable);
+    assert_eq!(analysis.score, 0);
+
+    // In the rack, but not in the dictionary
+    let analysis = analyse(&dictionary, &rack, "TACES", &Scoring::Countdown, &|_| true);
+    assert!(!analysis.in_dictionary);
+    assert!(analysis.formable);
+    assert_eq!(analysis.score, 0);
+
+    // Filtered out of the dictionary
+    let analysis = analyse(&dictionary, &rack, "TA", &Scoring::Countdown, &|word| {
+        !word.obscure
+    });
+    assert!(!analysis.is_valid());
+}
+
+#[test]
+fn test_analyse_blanks() {
+    let dictionary = fixture();
+    let rack = Rack::parse("CRATE?").unwrap();
+
+    let analysis = analyse(&dictionary, &rack, "CASTE", &Scoring::Scrabble, &|_| true);
+    assert!(analysis.is_valid());
+    // C3 + A1 + S0 (blank) + T1 + E1
+    assert_eq!(analysis.score, 6);
+    assert_eq!(analysis.best_score, 7);
+    assert_eq!(
+        texts(&analysis.best),
+        vec!["CRATE", "REACT", "TRACE", "CATERS", "CRATES", "REACTS"]
+    );
+}
@@ -0,0 +1,77 @@
+use crate::dictionary::{Entry, Word};
+use crate::rack::Rack;
+use crate::scoring::Scoring;
+use crate::WordTrie;
+use rayon::prelude::*;
+use std::io::{self, BufRead};
+
+#[cfg(test)]
+mod test;
+
+// How many racks to read before solving them in parallel
+const CHUNK_SIZE: usize = 4096;
+
+// The best words for a rack and their score, which is their length unless a scoring scheme is
+// given. Words are sorted alphabetically.
+pub fn best<'a>(
+    words: &'a WordTrie<Entry>,
+    rack: &Rack,
+    scoring: Option<&Scoring>,
+    usable: &(dyn Fn(&Word) -> bool + Sync),
+) -> (u32, Vec<&'a Word>) {
+    let mut best_score = 0;
+    let mut best = vec![];
+
+    for (entry, blanks) in rack.find(words) {
+        let score = match scoring {
+            Some(scoring) => {
+                let blanks: Vec<char> = blanks.into_iter().flatten().collect();
+                scoring.score(&entry.alphagram, &blanks)
+            }
+            None => entry.num_letters() as u32,
+        };
+        if score < best_score {
+            continue;
+        }
+
+        let mut usable: Vec<_> = entry.words.iter().filter(|word| usable(word)).collect();
+        if usable.is_empty() {
+            continue;
+        }
+        if score > best_score {
+            best_score = score;
+            best.clear();
+        }
+        best.append(&mut usable);
+    }
+
+    best.sort_unstable_by_key(|word| &word.text);
+    (best_score, best)
+}
+
+// Reads racks a line at a time and solves them in parallel, passing the results to `output` in
+// the same order as the input. Blank lines are skipped. Stops at the first error reading the input
+// or from `output`.
+pub fn solve_all<R: Send, E: From<io::Error>>(
+    input: impl BufRead,
+    solve: impl Fn(&str) -> R + Sync,
+    mut output: impl FnMut(&str, R) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut lines = input.lines();
+
+    loop {
+        let chunk = lines
+            .by_ref()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .take(CHUNK_SIZE)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        let results: Vec<_> = chunk.par_iter().map(|line| solve(line)).collect();
+        for (line, result) in chunk.iter().zip(results) {
+            output(line, result)?;
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+use crate::testutil::{fixture, texts};
+use anyhow::Result;
+
+#[test]
+fn test_best() {
+    let dictionary = fixture();
+
+    let (score, found) = best(
+        &dictionary.words,
+        &Rack::parse("TRACE").unwrap(),
+        None,
+        &|_| true,
+    );
+    assert_eq!(score, 5);
+    assert_eq!(texts(&found), vec!["CRATE", "REACT", "TRACE"]);
+
+    let (score, found) = best(
+        &dictionary.words,
+        &Rack::parse("TRACE?").unwrap(),
+        Some(&Scoring::Scrabble),
+        &|_| true,
+    );
+    assert_eq!(score, 7);
+    assert_eq!(
+        texts(&found),
+        vec!["CATERS", "CRATE", "CRATES", "REACT", "REACTS", "TRACE"]
+    );
+
+    let (score, found) = best(
+        &dictionary.words,
+        &Rack::parse("XYZ").unwrap(),
+        None,
+        &|_| true,
+    );
+    assert_eq!(score, 0);
+    assert!(found.is_empty());
+}
+
+#[test]
+fn test_solve_all_keeps_order() -> Result<()> {
+    let input: String = (0..10000).map(|i| format!("{}\n", i)).collect();
+
+    let mut output = vec![];
+    solve_all::<_, io::Error>(
+        input.as_bytes(),
+        |line| line.parse::<usize>().unwrap() * 2,
+        |line, result| {
+            output.push((line.to_owned(), result));
+            Ok(())
+        },
+    )?;
+
+    assert_eq!(output.len(), 10000);
+    for (i, (line, result)) in output.into_iter().enumerate() {
+        assert_eq!(line, i.to_string());
+        assert_eq!(result, i * 2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_solve_all_skips_blank_lines() -> Result<()> {
+    let mut output = vec![];
+    solve_all::<_, io::Error>(
+        "CAT\n\n  \nDOG\n".as_bytes(),
+        |line| line.len(),
+        |line, result| {
+            output.push((line.to_owned(), result));
+            Ok(())
+        },
+    )?;
+    assert_eq!(output, vec![("CAT".to_owned(), 3), ("DOG".to_owned(), 3)]);
+
+    Ok(())
+}
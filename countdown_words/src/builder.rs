@@ -0,0 +1,109 @@
+use crate::alphabet::Alphabet;
+use crate::error::{Error, LoadError};
+use crate::WordTrie;
+use std::io::prelude::*;
+
+#[cfg(test)]
+mod test;
+
+// The words sharing an alphagram, each with whether it's obscure
+pub type Anagrams = Vec<(String, bool)>;
+
+// Which words from a raw list make it into a dictionary. Lengths are counted in tiles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    // Any letter of the alphabet is allowed if this is empty
+    pub allowed: Vec<char>,
+}
+
+impl Filter {
+    // Whether an encoded word passes the filter
+    pub fn accepts(&self, alphabet: &Alphabet, word: &str) -> bool {
+        let len = word.chars().count();
+        len > 0
+            && self.min_len.is_none_or(|min_len| len >= min_len)
+            && self.max_len.is_none_or(|max_len| len <= max_len)
+            && word.chars().all(|c| {
+                if self.allowed.is_empty() {
+                    alphabet.is_letter(c)
+                } else {
+                    self.allowed.contains(&c)
+                }
+            })
+    }
+}
+
+// Reads a raw word list, a word per line with an optional trailing '*' marking it obscure, and
+// groups the words that pass the filter by alphagram. Returns how many lines were skipped.
+pub fn build(
+    reader: impl BufRead,
+    alphabet: &Alphabet,
+    filter: &Filter,
+) -> Result<(WordTrie<Anagrams>, usize), LoadError> {
+    let mut words: WordTrie<Anagrams> = WordTrie::new();
+    let mut skipped = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (word, obscure) = match line.strip_suffix('*') {
+            Some(word) => (alphabet.normalise(word), true),
+            None => (alphabet.normalise(line), false),
+        };
+        if !filter.accepts(alphabet, &alphabet.encode(&word)) {
+            skipped += 1;
+            continue;
+        }
+
+        let alphagram = alphabet.alphagram(&word);
+        match words.get_mut(&alphagram) {
+            Some(anagrams) => match anagrams.iter_mut().find(|(w, _)| *w == word) {
+                // Only obscure if every occurrence says so
+                Some((_, existing)) => *existing &= obscure,
+                None => anagrams.push((word, obscure)),
+            },
+            None => {
+                words.add(&alphagram, vec![(word, obscure)]);
+            }
+        }
+    }
+
+    Ok((words, skipped))
+}
+
+// Writes the grouped words as "ALPHAGRAM,WORD/WORD/..." lines, in alphagram order, in the format
+// `Dictionary::read` expects.
+pub fn write_csv(
+    words: &WordTrie<Anagrams>,
+    alphabet: &Alphabet,
+    mut writer: impl Write,
+) -> Result<(), Error> {
+    for (alphagram, anagrams) in words {
+        let mut anagrams = anagrams.clone();
+        anagrams.sort_unstable();
+        let anagrams: Vec<_> = anagrams
+            .iter()
+            .map(|(word, obscure)| {
+                if *obscure {
+                    format!("{}*", word)
+                } else {
+                    word.clone()
+                }
+            })
+            .collect();
+        writeln!(
+            writer,
+            "{},{}",
+            alphabet.decode(&alphagram),
+            anagrams.join("/")
+        )
+        .map_err(Error::Output)?;
+    }
+    Ok(())
+}
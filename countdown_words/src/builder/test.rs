@@ -0,0 +1,98 @@
+use super::*;
+use crate::dictionary::Dictionary;
+use anyhow::Result;
+
+fn build_csv(list: &str, alphabet: &Alphabet, filter: &Filter) -> Result<(String, usize)> {
+    let (words, skipped) = build(list.as_bytes(), alphabet, filter)?;
+    let mut csv = vec![];
+    write_csv(&words, alphabet, &mut csv)?;
+    Ok((String::from_utf8(csv)?, skipped))
+}
+
+#[test]
+fn test_build() -> Result<()> {
+    let filter = Filter {
+        min_len: Some(2),
+        max_len: Some(5),
+        ..Filter::default()
+    };
+    let (csv, skipped) = build_csv(
+        "cat\nact\n\nta*\nAt\nACT\na\ncrates\ndon't\ntrace\n",
+        &Alphabet::new(),
+        &filter,
+    )?;
+    assert_eq!(csv, "ACERT,TRACE\nACT,ACT/CAT\nAT,AT/TA*\n");
+    assert_eq!(skipped, 3);
+
+    // The output loads back as a dictionary
+    let mut dictionary = Dictionary::new();
+    dictionary.read("built", csv.as_bytes())?;
+    assert_eq!(dictionary.words.count(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_build_allowed() -> Result<()> {
+    let filter = Filter {
+        allowed: "ACT".chars().collect(),
+        ..Filter::default()
+    };
+    let (csv, skipped) = build_csv("cat\ncats\ntact\n", &Alphabet::new(), &filter)?;
+    assert_eq!(csv, "ACT,CAT\nACTT,TACT\n");
+    assert_eq!(skipped, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_build_tiles() -> Result<()> {
+    let alphabet = Alphabet::parse("welsh")?;
+    let filter = Filter {
+        max_len: Some(3),
+        ..Filter::default()
+    };
+    // LLAN is three tiles, so it's kept, and its alphagram sorts LL after the single letters
+    let (csv, skipped) = build_csv("llan\nllanfair\n", &alphabet, &filter)?;
+    assert_eq!(csv, "ANLL,LLAN\n");
+    assert_eq!(skipped, 1);
+
+    // Sorting puts the two Ds of DYD next to each other, and the C and H of HACIA, which must
+    // still load back as separate letters
+    for (name, list, expected) in [
+        ("welsh", "dyd\nddy\n", "DDY,DYD\nYDD,DDY\n"),
+        ("spanish", "hacia\nchica\n", "AACHI,HACIA\nACICH,CHICA\n"),
+    ] {
+        let alphabet = Alphabet::parse(name)?;
+        let (csv, _) = build_csv(list, &alphabet, &Filter::default())?;
+        assert_eq!(csv, expected);
+
+        let mut dictionary = Dictionary::with_alphabet(alphabet);
+        dictionary.read("built", csv.as_bytes())?;
+        assert_eq!(dictionary.words.count(), 2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_write_error() -> Result<()> {
+    struct Full;
+    impl Write for Full {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::WriteZero.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let alphabet = Alphabet::new();
+    let (words, _) = build("cat\n".as_bytes(), &alphabet, &Filter::default())?;
+    assert!(matches!(
+        write_csv(&words, &alphabet, Full),
+        Err(Error::Output(_))
+    ));
+
+    Ok(())
+}
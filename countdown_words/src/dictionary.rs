@@ -0,0 +1,209 @@
+use crate::alphabet::Alphabet;
+use crate::error::{InputError, LoadError};
+use crate::WordTrie;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::Path;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Word {
+    pub text: String,
+    // Marked with a trailing '*' in the word list
+    pub obscure: bool,
+    // A bit per loaded word list that contains this word
+    pub sources: u32,
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub alphagram: String,
+    pub words: Vec<Word>,
+}
+
+impl Entry {
+    pub fn num_letters(&self) -> usize {
+        self.alphagram.chars().count()
+    }
+}
+
+// One or more word lists merged into a single trie, remembering which list each word came from.
+#[derive(Debug)]
+pub struct Dictionary {
+    pub words: WordTrie<Entry>,
+    pub alphabet: Alphabet,
+    names: Vec<String>,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Dictionary {
+    pub fn new() -> Self {
+        Self::with_alphabet(Alphabet::new())
+    }
+
+    pub fn with_alphabet(alphabet: Alphabet) -> Self {
+        Self {
+            words: WordTrie::new(),
+            alphabet,
+            names: vec![],
+        }
+    }
+
+    // Accepts "NAME=PATH", or just a path in which case the file stem is used as the name
+    pub fn load_spec(&mut self, spec: &str) -> Result<(), LoadError> {
+        match spec.split_once('=') {
+            Some((name, path)) => self.load(name, path),
+            None => {
+                let name = Path::new(spec)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or(spec);
+                self.load(name, spec)
+            }
+        }
+    }
+
+    // Loads a word list of "ALPHAGRAM,WORD/WORD/..." lines, keyed by the sorted alphagram. Words
+    // are normalised, and alphagrams encoded, by the dictionary's alphabet. A word with a trailing
+    // '*' (e.g. "AT,AT/TA*") is obscure, and left out by --no-obscure.
+    pub fn load(&mut self, name: &str, path: &str) -> Result<(), LoadError> {
+        let f = File::open(path)?;
+        self.read(name, BufReader::new(f))
+    }
+
+    pub fn read(&mut self, name: &str, mut reader: impl BufRead) -> Result<(), LoadError> {
+        if self.names.iter().any(|n| n == name) {
+            return Err(LoadError::LoadedTwice(name.to_owned()));
+        }
+        if self.names.len() == u32::BITS as usize {
+            return Err(LoadError::TooManyLists);
+        }
+        let source = 1 << self.names.len();
+
+        // Parsed in full before anything is merged, so a bad line leaves the dictionary as it was
+        let mut entries = vec![];
+        let mut line_number = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let bad_line = || LoadError::BadLine {
+                line_number,
+                line: line.trim().to_owned(),
+            };
+
+            let mut fields: Vec<_> = line.trim().split(',').map(str::to_owned).collect();
+            if fields.len() != 2 {
+                return Err(bad_line());
+            }
+
+            let anagrams = fields
+                .pop()
+                .unwrap()
+                .split('/')
+                .map(str::trim)
+                .filter(|word| !word.is_empty())
+                .map(|word| match word.strip_suffix('*') {
+                    Some(word) => Word {
+                        text: self.alphabet.normalise(word),
+                        obscure: true,
+                        sources: source,
+                    },
+                    None => Word {
+                        text: self.alphabet.normalise(word),
+                        obscure: false,
+                        sources: source,
+                    },
+                })
+                .collect::<Vec<_>>();
+            let Some(first) = anagrams.first() else {
+                return Err(bad_line());
+            };
+            // Keyed by the words' own tiles: encoding the sorted column could join letters that
+            // only ended up next to each other by sorting, such as the C and H of "AACHI"
+            let alphagram = self.alphabet.alphagram(&first.text);
+            let letters = |text: &str| make_alphagram(&self.alphabet.decode(text));
+            if alphagram.is_empty()
+                || letters(&self.alphabet.normalise(&fields.pop().unwrap())) != letters(&alphagram)
+                || anagrams
+                    .iter()
+                    .any(|word| self.alphabet.alphagram(&word.text) != alphagram)
+            {
+                return Err(bad_line());
+            }
+            entries.push((alphagram, anagrams));
+        }
+
+        self.names.push(name.to_owned());
+        for (alphagram, anagrams) in entries {
+            match self.words.get_mut(&alphagram) {
+                Some(entry) => {
+                    for word in anagrams {
+                        match entry.words.iter_mut().find(|w| w.text == word.text) {
+                            // Only obscure if every list that has it says so
+                            Some(existing) => {
+                                existing.sources |= source;
+                                existing.obscure &= word.obscure;
+                            }
+                            None => entry.words.push(word),
+                        }
+                    }
+                }
+                None => {
+                    self.words.add(
+                        &alphagram,
+                        Entry {
+                            alphagram: alphagram.clone(),
+                            words: anagrams,
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn num_lists(&self) -> usize {
+        self.names.len()
+    }
+
+    // The sources bitmask for the named word lists
+    pub fn mask(&self, names: &[String]) -> Result<u32, InputError> {
+        names.iter().try_fold(0, |mask, name| {
+            match self.names.iter().position(|n| n == name) {
+                Some(i) => Ok(mask | 1 << i),
+                None => Err(InputError::UnknownList(name.clone())),
+            }
+        })
+    }
+
+    pub fn names(&self, sources: u32) -> Vec<&str> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| sources & (1 << i) != 0)
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+}
+
+pub fn make_alphagram(word: &str) -> String {
+    let mut alphagram: Vec<char> = word.chars().collect();
+    alphagram.sort_unstable();
+    alphagram.into_iter().collect()
+}
@@ -0,0 +1,194 @@
+use super::*;
+use crate::testutil::fixture;
+use anyhow::Result;
+
+fn read(contents: &str) -> Result<Dictionary> {
+    let mut dictionary = Dictionary::new();
+    dictionary.read("test", contents.as_bytes())?;
+    Ok(dictionary)
+}
+
+#[test]
+fn test_read() -> Result<()> {
+    let dictionary = read("ACT,CAT/ACT\n\nAT,AT/TA*\n")?;
+    assert_eq!(dictionary.words.count(), 2);
+
+    let entry = dictionary.words.get("AT").unwrap();
+    assert_eq!(entry.num_letters(), 2);
+    assert_eq!(
+        entry.words,
+        vec![
+            Word {
+                text: "AT".to_string(),
+                obscure: false,
+                sources: 1
+            },
+            Word {
+                text: "TA".to_string(),
+                obscure: true,
+                sources: 1
+            }
+        ]
+    );
+
+    // Alphagrams don't have to be sorted in the file
+    let dictionary = read("TCA,CAT\n")?;
+    assert!(dictionary.words.contains("ACT"));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_malformed() {
+    assert!(read("ACT\n").is_err());
+    assert!(read("ACT,CAT,ACT\n").is_err());
+    assert!(read(",CAT\n").is_err());
+    assert!(read("ACT,\n").is_err());
+    assert!(read("ACT,CAT/DOG\n").is_err());
+    assert!(read("ACT,CATS\n").is_err());
+
+    let err = read("ACT,CAT\nAT\n").unwrap_err();
+    assert_eq!(err.to_string(), "Bad word list line 2: AT");
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(LoadError::BadLine { line_number: 2, .. })
+    ));
+}
+
+#[test]
+fn test_read_failure_leaves_dictionary_unchanged() -> Result<()> {
+    let mut dictionary = fixture();
+    let before = dictionary.words.get("ACT").unwrap().words.clone();
+    assert!(dictionary
+        .read(
+            "extra",
+            "ACT,TAC
+AAB,ABA
+AT
+"
+            .as_bytes()
+        )
+        .is_err());
+    assert_eq!(dictionary.num_lists(), 1);
+    assert_eq!(dictionary.words.get("ACT").unwrap().words, before);
+    assert!(dictionary.words.get("AAB").is_none());
+
+    // The name wasn't taken, so a corrected list loads under it
+    dictionary.read(
+        "extra",
+        "ACT,TAC
+AAB,ABA
+"
+        .as_bytes(),
+    )?;
+    assert_eq!(dictionary.num_lists(), 2);
+    assert!(dictionary.words.get("AAB").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_multiple_lists() -> Result<()> {
+    let mut dictionary = fixture();
+    dictionary.read("extra", "ACT,ACT*/TAC*\nAAB,ABA\n".as_bytes())?;
+    assert_eq!(dictionary.num_lists(), 2);
+    assert!(dictionary.read("extra", "".as_bytes()).is_err());
+
+    let entry = dictionary.words.get("ACT").unwrap();
+    let sources: Vec<_> = entry
+        .words
+        .iter()
+        .map(|word| {
+            (
+                word.text.as_str(),
+                dictionary.names(word.sources),
+                word.obscure,
+            )
+        })
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            ("CAT", vec!["small"], false),
+            ("ACT", vec!["small", "extra"], false),
+            ("TAC", vec!["extra"], true)
+        ]
+    );
+
+    assert_eq!(dictionary.mask(&["extra".to_string()])?, 2);
+    assert!(dictionary.mask(&["missing".to_string()]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_golden() -> Result<()> {
+    let dictionary = fixture();
+    let rack = crate::rack::Rack::parse("crates")?;
+
+    let mut found = dictionary.words.find_substrings(&rack.letters);
+    found.sort_unstable_by_key(|entry| (entry.num_letters(), &entry.words));
+    found.dedup_by_key(|entry| &entry.alphagram);
+
+    let found: Vec<_> = found
+        .into_iter()
+        .map(|entry| {
+            let words: Vec<_> = entry.words.iter().map(|word| word.text.as_str()).collect();
+            format!("{}: {}", entry.num_letters(), words.join("/"))
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            "2: AT/TA",
+            "3: CAT/ACT",
+            "3: SAT",
+            "3: SET",
+            "4: CATS/CAST/SCAT/ACTS",
+            "4: EATS/SEAT/TEAS",
+            "5: CASTE",
+            "5: CRATE/TRACE/REACT",
+            "6: CRATES/REACTS/CATERS",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_alphabet() -> Result<()> {
+    let mut dictionary = Dictionary::with_alphabet(Alphabet::parse("spanish")?);
+    dictionary.read("test", "eechl,leche\nacell,calle\noña,año\n".as_bytes())?;
+
+    let alphabet = &dictionary.alphabet;
+    let rack = crate::rack::Rack::parse_in("ll a c ?", alphabet)?;
+    let found: Vec<_> = rack
+        .find(&dictionary.words)
+        .into_iter()
+        .flat_map(|(entry, _)| &entry.words)
+        .map(|word| word.text.as_str())
+        .collect();
+    assert_eq!(found, vec!["CALLE"]);
+    assert!(dictionary.words.get(&alphabet.alphagram("AÑO")).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_read_adjacent_tile_letters() -> Result<()> {
+    // Sorting puts the C and H of HACIA side by side, but they are still two letters
+    let mut dictionary = Dictionary::with_alphabet(Alphabet::parse("spanish")?);
+    dictionary.read("test", "AACHI,HACIA\nACHO,OCHA\n".as_bytes())?;
+    let num_letters = |word| {
+        let alphagram = dictionary.alphabet.alphagram(word);
+        dictionary.words.get(&alphagram).map(Entry::num_letters)
+    };
+    assert_eq!(num_letters("HACIA"), Some(5));
+    assert_eq!(num_letters("OCHA"), Some(3));
+
+    let mut dictionary = Dictionary::with_alphabet(Alphabet::parse("welsh")?);
+    dictionary.read("test", "DDY,DYD\n".as_bytes())?;
+    assert!(dictionary.read("other", "ADDY,DYD\n".as_bytes()).is_err());
+
+    Ok(())
+}
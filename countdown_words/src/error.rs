@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io;
+
+// Anything that can go wrong, split so callers can tell a broken word list from bad input
+#[derive(Debug)]
+pub enum Error {
+    Load(LoadError),
+    Input(InputError),
+    // Writing results, such as a built word list
+    Output(io::Error),
+}
+
+// Problems reading word lists and letter values
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    BadLine { line_number: usize, line: String },
+    LoadedTwice(String),
+    TooManyLists,
+    BadValuesLine { line_number: usize, line: String },
+}
+
+// Problems with racks, patterns and options given by the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    // Decoded, so multi-letter tiles read as they were typed
+    BadLetter(String),
+    TooManyLetters { len: usize, max_len: usize },
+    UnterminatedClass(String),
+    EmptyClass,
+    BadLength(String),
+    BadVowels(String),
+    UnknownList(String),
+    UnknownAlphabet(String),
+    // Neither a built-in scoring scheme nor a letter values file
+    UnknownScoring(String),
+    // A built-in scoring scheme used with an alphabet it has no values for
+    NoLetterValues(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Load(e) => e.fmt(f),
+            Error::Input(e) => e.fmt(f),
+            Error::Output(e) => write!(f, "Couldn't write output: {}", e),
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "Couldn't read input: {}", e),
+            LoadError::BadLine { line_number, line } => {
+                write!(f, "Bad word list line {}: {}", line_number, line)
+            }
+            LoadError::LoadedTwice(name) => write!(f, "Word list {} loaded twice", name),
+            LoadError::TooManyLists => write!(f, "Too many word lists"),
+            LoadError::BadValuesLine { line_number, line } => {
+                write!(f, "Bad letter values line {}: {}", line_number, line)
+            }
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::BadLetter(letter) => write!(f, "{} is not a letter", letter),
+            InputError::TooManyLetters { len, max_len } => {
+                write!(f, "Too many letters: {}, at most {} allowed", len, max_len)
+            }
+            InputError::UnterminatedClass(text) => write!(f, "Unterminated class in {}", text),
+            InputError::EmptyClass => write!(f, "Empty wildcard class"),
+            InputError::BadLength(length) => write!(f, "Bad length {}", length),
+            InputError::BadVowels(vowels) => write!(f, "Bad number of vowels {}", vowels),
+            InputError::UnknownList(name) => write!(f, "No word list named {}", name),
+            InputError::UnknownAlphabet(name) => write!(f, "Unknown alphabet {}", name),
+            InputError::UnknownScoring(name) => write!(f, "Unknown scoring {}", name),
+            InputError::NoLetterValues(name) => write!(
+                f,
+                "{} has no letter values for this alphabet, use a letter values file",
+                name
+            ),
+        }
+    }
+}
+
+// Display comes straight from a wrapped load or input error, so its source is the wrapped
+// error's source
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Load(e) => e.source(),
+            Error::Input(e) => e.source(),
+            Error::Output(e) => Some(e),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<LoadError> for Error {
+    fn from(e: LoadError) -> Self {
+        Error::Load(e)
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Self {
+        Error::Input(e)
+    }
+}
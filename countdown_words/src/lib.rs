@@ -0,0 +1,18 @@
+pub mod alphabet;
+pub mod analysis;
+pub mod batch;
+pub mod builder;
+pub mod dictionary;
+pub mod error;
+pub mod phrases;
+pub mod query;
+pub mod rack;
+pub mod scoring;
+pub mod simulate;
+pub mod trie;
+
+#[cfg(test)]
+pub(crate) mod testutil;
+
+pub use error::{Error, InputError, LoadError};
+pub use trie::{Wildcard, WordTrie};
@@ -1,31 +1,214 @@
 use anyhow::{anyhow, Result};
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::env;
 use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufReader;
-use std::io::{self, Write};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 
-fn main() -> Result<()> {
-    let f = File::open("words.csv")?;
-    let mut reader = BufReader::new(f);
+use countdown_words::alphabet::Alphabet;
+use countdown_words::analysis;
+use countdown_words::batch;
+use countdown_words::builder;
+use countdown_words::dictionary::{Dictionary, Entry, Word};
+use countdown_words::phrases::{self, PhraseLimits};
+use countdown_words::query::{self, Pattern, Query};
+use countdown_words::rack::Rack;
+use countdown_words::scoring::Scoring;
+use countdown_words::simulate;
+use countdown_words::{InputError, Wildcard};
 
-    let mut words = WordTrie::new();
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Layout {
+    // All anagrams of an alphagram on one line, slash-separated
+    #[default]
+    Joined,
+    OnePerLine,
+    // A line per alphagram, followed by its anagrams indented
+    Grouped,
+}
 
-    loop {
-        let mut line = String::new();
-        if reader.read_line(&mut line)? == 0 {
-            break;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Command {
+    #[default]
+    Solve,
+    // Solve lots of random racks and report statistics as CSV
+    Simulate,
+    // Solve racks read from a file or stdin, a line each, in parallel
+    Batch,
+    // Turn a raw word list into a word list CSV
+    Build,
+}
+
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    scoring: Option<Scoring>,
+    // Kept as given until the alphabet is known, then parsed into `scoring`
+    score: Option<String>,
+    top: Option<usize>,
+    query: Query,
+    // Kept as given until the alphabet is known, then encoded into the query
+    pattern: Option<String>,
+    required: String,
+    excluded: String,
+    alphabet: Alphabet,
+    layout: Layout,
+    alphabetical: bool,
+    no_obscure: bool,
+    dictionaries: Vec<String>,
+    only: Vec<String>,
+    phrases: bool,
+    phrase_letters: Option<usize>,
+    max_words: usize,
+    min_len: usize,
+    interactive: bool,
+    declared: Option<String>,
+    vowels: (usize, usize),
+    racks: usize,
+    seed: Option<u64>,
+    input: Option<String>,
+    output: Option<String>,
+    chars: Vec<char>,
+    // The most tiles, wildcards included, a rack may have
+    max_letters: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            command: Command::default(),
+            scoring: None,
+            score: None,
+            top: None,
+            query: Query::default(),
+            pattern: None,
+            required: String::new(),
+            excluded: String::new(),
+            alphabet: Alphabet::new(),
+            layout: Layout::default(),
+            alphabetical: false,
+            no_obscure: false,
+            dictionaries: vec![],
+            only: vec![],
+            phrases: false,
+            phrase_letters: None,
+            max_words: 3,
+            min_len: 3,
+            interactive: false,
+            declared: None,
+            vowels: (3, 5),
+            racks: 10000,
+            seed: None,
+            input: None,
+            output: None,
+            chars: vec![],
+            max_letters: 16,
         }
-        let mut fields: Vec<_> = line.trim().split(',').map(str::to_owned).collect();
-        if fields.len() != 2 {
-            return Err(anyhow!("Bad word list file"));
+    }
+}
+
+fn parse_args() -> Result<Options> {
+    let mut options = Options::default();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("{} needs a value", arg));
+        match arg.as_str() {
+            "--score" => options.score = Some(value()?),
+            "--top" => options.top = Some(value()?.parse()?),
+            "--pattern" => options.pattern = Some(value()?),
+            "--length" => options.query.set_length(&value()?)?,
+            "--require" => options.required = value()?,
+            "--exclude" => options.excluded = value()?,
+            "--alphabet" => options.alphabet = Alphabet::parse(&value()?)?,
+            "--tiles" => options
+                .alphabet
+                .set_tiles(&value()?.split(',').collect::<Vec<_>>()),
+            "--strip-accents" => options.alphabet.set_strip_accents(true),
+            "--keep-accents" => options.alphabet.set_strip_accents(false),
+            "--one-per-line" => options.layout = Layout::OnePerLine,
+            "--group" => options.layout = Layout::Grouped,
+            "--alphabetical" => options.alphabetical = true,
+            "--no-obscure" => options.no_obscure = true,
+            "--dict" => options.dictionaries.push(value()?),
+            "--only" => options
+                .only
+                .extend(value()?.split(',').map(|name| name.trim().to_owned())),
+            "--phrases" => options.phrases = true,
+            "-i" | "--interactive" => options.interactive = true,
+            "--declared" => options.declared = Some(value()?),
+            "--use" => options.phrase_letters = Some(value()?.parse()?),
+            "--max-words" => options.max_words = value()?.parse()?,
+            "--min-length" => options.min_len = value()?.parse()?,
+            "--vowels" => {
+                let vowels = value()?;
+                let (min, max) =
+                    query::parse_range(&vowels).ok_or(InputError::BadVowels(vowels))?;
+                options.vowels = (min.unwrap_or(0), max.unwrap_or(simulate::RACK_SIZE));
+            }
+            "--racks" => options.racks = value()?.parse()?,
+            "--seed" => options.seed = Some(value()?.parse()?),
+            "simulate" if options.command == Command::Solve => options.command = Command::Simulate,
+            "--max-letters" => options.max_letters = value()?.parse()?,
+            "--output" => options.output = Some(value()?),
+            "--chars" => options.chars = value()?.chars().collect(),
+            "batch" if options.command == Command::Solve => options.command = Command::Batch,
+            "build" if options.command == Command::Solve => options.command = Command::Build,
+            _ if matches!(options.command, Command::Batch | Command::Build)
+                && options.input.is_none()
+                && (arg == "-" || !arg.starts_with('-')) =>
+            {
+                options.input = Some(arg)
+            }
+            _ => return Err(anyhow!("Unknown argument {}", arg)),
         }
+    }
 
-        let anagrams = fields.pop().unwrap();
-        let mut alphagram: Vec<char> = fields.pop().unwrap().trim().chars().collect();
-        alphagram.sort_unstable();
-        let alphagram: String = alphagram.into_iter().collect();
-        words.add(&alphagram, (alphagram.len(), anagrams));
+    let alphabet = &options.alphabet;
+    if let Some(score) = &options.score {
+        options.scoring = Some(Scoring::parse(score, alphabet)?);
+    }
+    if let Some(pattern) = &options.pattern {
+        options.query.pattern = Some(Pattern::parse(&alphabet.encode(pattern))?);
+    }
+    options.query.required = alphabet.encode(&options.required).chars().collect();
+    options.query.excluded = alphabet.encode(&options.excluded).chars().collect();
+    options.chars = alphabet
+        .encode(&options.chars.iter().collect::<String>())
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    if options.top.is_some() && options.scoring.is_none() {
+        options.scoring = Some(Scoring::Countdown);
+    }
+
+    Ok(options)
+}
+
+fn main() -> Result<()> {
+    let options = parse_args()?;
+    if options.command == Command::Build {
+        return run_build(&options);
+    }
+
+    let mut dictionary = Dictionary::with_alphabet(options.alphabet.clone());
+    if options.dictionaries.is_empty() {
+        dictionary.load("words", "words.csv")?;
+    }
+    for spec in &options.dictionaries {
+        dictionary.load_spec(spec)?;
+    }
+
+    match options.command {
+        Command::Simulate => return run_simulation(&dictionary, &options),
+        Command::Batch => return run_batch(&dictionary, &options),
+        Command::Build => unreachable!(),
+        Command::Solve => {}
+    }
+
+    if options.interactive {
+        return repl(&dictionary, options);
     }
 
     print!("Enter letters: ");
@@ -33,69 +216,569 @@ fn main() -> Result<()> {
 
     let mut letters = String::new();
     io::stdin().read_line(&mut letters)?;
-    letters.make_ascii_uppercase();
-    let mut letters: Vec<char> = letters.trim().chars().collect();
-    letters.sort_unstable();
-    let letters: String = letters.into_iter().collect();
+    match &options.declared {
+        Some(declared) => compare(&dictionary, &options, &letters, declared),
+        None => solve(&dictionary, &options, &letters),
+    }
+}
+
+const REPL_HELP: &str = "\
+Enter a rack to solve it, or a command:
+  :len N, :len MIN-MAX  only show words of these lengths (:len off to show all)
+  :conundrum [LETTERS]  words using all the letters (of the last rack by default)
+  :define WORD          look up a word and its anagrams
+  :vs WORD              compare a declared word with the best for the last rack
+  :history              list previous racks, !N or !! to solve one again
+  :quit                 exit";
 
-    let mut substrings = words.find_substrings(letters);
-    substrings.sort_unstable();
-    substrings.dedup();
-    for (len, word) in substrings {
-        println!("{}: {}", len, word);
+// Keeps the dictionary loaded and answers racks and commands until told to quit
+fn repl(dictionary: &Dictionary, mut options: Options) -> Result<()> {
+    let mut history: Vec<String> = vec![];
+
+    loop {
+        print!("Enter letters: ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+        let line = line.trim();
+
+        let rack = if line == "!!" {
+            history.last().cloned()
+        } else if let Some(n) = line.strip_prefix('!') {
+            n.parse::<usize>()
+                .ok()
+                .and_then(|n| history.get(n.wrapping_sub(1)))
+                .cloned()
+        } else {
+            None
+        };
+
+        let result = match (line.split_once(' ').unwrap_or((line, "")), rack) {
+            (_, Some(rack)) => {
+                println!("{}", rack);
+                let result = solve(dictionary, &options, &rack);
+                if result.is_ok() {
+                    history.push(rack);
+                }
+                result
+            }
+            (("", _), None) => Ok(()),
+            ((":quit" | ":q", _), None) => return Ok(()),
+            ((":help", _), None) => {
+                println!("{}", REPL_HELP);
+                Ok(())
+            }
+            ((":len", "off"), None) => {
+                options.query.min_len = None;
+                options.query.max_len = None;
+                Ok(())
+            }
+            ((":len", length), None) => Ok(options.query.set_length(length.trim())?),
+            ((":conundrum", letters), None) => {
+                let letters = if letters.trim().is_empty() {
+                    history.last().map(String::as_str).unwrap_or("")
+                } else {
+                    letters
+                };
+                conundrum(dictionary, &options, letters)
+            }
+            ((":vs", word), None) => match history.last() {
+                Some(rack) => compare(dictionary, &options, rack, word),
+                None => Err(anyhow!("No rack to compare with yet")),
+            },
+            ((":define", word), None) => {
+                define(dictionary, word);
+                Ok(())
+            }
+            ((":history", _), None) => {
+                for (i, rack) in history.iter().enumerate() {
+                    println!("{:4}  {}", i + 1, rack);
+                }
+                Ok(())
+            }
+            ((command, _), None) if command.starts_with([':', '!']) => {
+                Err(anyhow!("Unknown command {}, try :help", command))
+            }
+            (_, None) => {
+                // Only racks that could be solved, so !! and :vs never pick up a typo
+                let result = solve(dictionary, &options, line);
+                if result.is_ok() {
+                    history.push(line.to_owned());
+                }
+                result
+            }
+        };
+
+        if let Err(e) = result {
+            println!("Error: {}", e);
+        }
+    }
+}
+
+fn conundrum(dictionary: &Dictionary, options: &Options, letters: &str) -> Result<()> {
+    let rack = parse_rack(dictionary, options, letters)?;
+    if rack.letters.is_empty() || !rack.wildcards.is_empty() {
+        return Err(anyhow!("A conundrum needs letters and no wildcards"));
     }
 
+    let sources = sources(dictionary, options)?;
+    let usable = usable(options, sources);
+    let words: Vec<_> = dictionary
+        .words
+        .get(&rack.letters)
+        .into_iter()
+        .flat_map(|entry| &entry.words)
+        .filter(|word| usable(word))
+        .map(|word| label(word, dictionary))
+        .collect();
+
+    if words.is_empty() {
+        println!(
+            "No word uses all of {}",
+            dictionary.alphabet.decode(&rack.letters)
+        );
+    } else {
+        println!("{}", words.join("/"));
+    }
     Ok(())
 }
 
-#[derive(Debug)]
-struct WordTrie<T> {
-    entries: HashMap<char, WordTrie<T>>,
-    value: Option<T>,
+fn define(dictionary: &Dictionary, word: &str) {
+    let word = dictionary.alphabet.normalise(word);
+    let entry = dictionary.words.get(&dictionary.alphabet.alphagram(&word));
+
+    match entry.and_then(|entry| entry.words.iter().find(|w| w.text == word)) {
+        Some(found) => {
+            let obscure = if found.obscure { ", obscure" } else { "" };
+            println!(
+                "{} is valid ({}{})",
+                word,
+                dictionary.names(found.sources).join(", "),
+                obscure
+            );
+        }
+        None => println!("{} is not in the dictionary", word),
+    }
+
+    let anagrams: Vec<_> = entry
+        .into_iter()
+        .flat_map(|entry| &entry.words)
+        .filter(|w| w.text != word)
+        .map(|w| label(w, dictionary))
+        .collect();
+    if !anagrams.is_empty() {
+        println!("Anagrams: {}", anagrams.join("/"));
+    }
 }
 
-impl<'a, T> WordTrie<T> {
-    fn new() -> Self {
-        Self {
-            entries: HashMap::new(),
-            value: None,
+fn run_batch(dictionary: &Dictionary, options: &Options) -> Result<()> {
+    let sources = sources(dictionary, options)?;
+    let usable = usable(options, sources);
+
+    let input: Box<dyn BufRead> = match options.input.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let mut stdout = BufWriter::new(io::stdout().lock());
+
+    writeln!(stdout, "rack,score,words")?;
+    batch::solve_all::<_, anyhow::Error>(
+        input,
+        |line| {
+            let rack = parse_rack(dictionary, options, line)?;
+            let (score, best) =
+                batch::best(&dictionary.words, &rack, options.scoring.as_ref(), &usable);
+            let best: Vec<_> = best.iter().map(|word| word.text.as_str()).collect();
+            Ok(format!("{},{}", score, best.join("/")))
+        },
+        |line, result: Result<String>| {
+            let line = line.trim();
+            match result {
+                Ok(result) => writeln!(stdout, "{},{}", line, result)?,
+                Err(e) => {
+                    eprintln!("{}: {}", line, e);
+                    writeln!(stdout, "{},,", line)?;
+                }
+            }
+            Ok(())
+        },
+    )?;
+
+    stdout.flush()?;
+    Ok(())
+}
+
+fn run_build(options: &Options) -> Result<()> {
+    let input: Box<dyn BufRead> = match options.input.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+    };
+    let filter = builder::Filter {
+        min_len: options.query.min_len,
+        max_len: options.query.max_len,
+        allowed: options.chars.clone(),
+    };
+
+    let (words, skipped) = builder::build(input, &options.alphabet, &filter)?;
+    match options.output.as_deref() {
+        None | Some("-") => builder::write_csv(&words, &options.alphabet, io::stdout().lock())?,
+        Some(path) => {
+            let mut output = BufWriter::new(File::create(path)?);
+            builder::write_csv(&words, &options.alphabet, &mut output)?;
+            output.flush()?;
+        }
+    }
+    eprintln!(
+        "Wrote {} alphagrams, skipped {} lines",
+        words.count(),
+        skipped
+    );
+
+    Ok(())
+}
+
+fn run_simulation(dictionary: &Dictionary, options: &Options) -> Result<()> {
+    let (min_vowels, max_vowels) = options.vowels;
+    if min_vowels > max_vowels || max_vowels > simulate::RACK_SIZE {
+        return Err(anyhow!(
+            "Vowels must be between 0 and {}",
+            simulate::RACK_SIZE
+        ));
+    }
+
+    let sources = sources(dictionary, options)?;
+    let usable = usable(options, sources);
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    println!("vowels,statistic,value,count,fraction");
+    for vowels in min_vowels..=max_vowels {
+        let stats = simulate::simulate(&dictionary.words, &mut rng, vowels, options.racks, &usable);
+        let fraction = |count: usize| count as f64 / stats.racks as f64;
+
+        for (len, &count) in stats.max_lens.iter().enumerate() {
+            println!(
+                "{},max_length,{},{},{:.4}",
+                vowels,
+                len,
+                count,
+                fraction(count)
+            );
+        }
+        println!(
+            "{},nine,,{},{:.4}",
+            vowels,
+            stats.nines(),
+            fraction(stats.nines())
+        );
+        for (word, count) in stats.top_words(options.top.unwrap_or(10)) {
+            println!(
+                "{},best_word,{},{},{:.4}",
+                vowels,
+                word,
+                count,
+                fraction(count)
+            );
         }
     }
 
-    fn add(&mut self, word: &str, value: T) {
-        if word.is_empty() {
-            self.value = Some(value);
+    Ok(())
+}
+
+fn compare(dictionary: &Dictionary, options: &Options, rack: &str, declared: &str) -> Result<()> {
+    let sources = sources(dictionary, options)?;
+    let usable = usable(options, sources);
+    let scoring = options.scoring.as_ref().unwrap_or(&Scoring::Countdown);
+
+    let rack = parse_rack(dictionary, options, rack)?;
+    let analysis = analysis::analyse(dictionary, &rack, declared, scoring, &usable);
+    let words = |words: &[&Word]| {
+        if words.is_empty() {
+            "none".to_string()
         } else {
-            let mut chars = word.chars();
-            let first = chars.next().unwrap();
-            let rest: String = chars.collect();
+            let words: Vec<_> = words.iter().map(|word| label(word, dictionary)).collect();
+            words.join("/")
+        }
+    };
+
+    match (analysis.in_dictionary, analysis.formable) {
+        (true, true) => println!("{} scores {}", analysis.word, analysis.score),
+        (false, true) => println!("{} is not in the dictionary", analysis.word),
+        (true, false) => println!("{} can't be made from the rack", analysis.word),
+        (false, false) => println!(
+            "{} is not in the dictionary and can't be made from the rack",
+            analysis.word
+        ),
+    }
+
+    println!("Best: {} ({})", analysis.best_score, words(&analysis.best));
+    println!("Same length: {}", words(&analysis.same_length));
+    println!("Longer: {}", words(&analysis.longer));
+    if analysis.is_valid() {
+        println!("Short of best by {}", analysis.best_score - analysis.score);
+    }
+
+    Ok(())
+}
+
+fn sources(dictionary: &Dictionary, options: &Options) -> Result<u32> {
+    if options.only.is_empty() {
+        Ok(u32::MAX)
+    } else {
+        Ok(dictionary.mask(&options.only)?)
+    }
+}
+
+// Whether a word is in one of the chosen lists, and not obscure if those are left out
+fn usable(options: &Options, sources: u32) -> impl Fn(&Word) -> bool + '_ {
+    move |word| word.sources & sources != 0 && !(options.no_obscure && word.obscure)
+}
+
+fn parse_rack(dictionary: &Dictionary, options: &Options, rack: &str) -> Result<Rack> {
+    let rack = Rack::parse_in(rack, &dictionary.alphabet)?;
+    rack.check_len(options.max_letters)?;
+    Ok(rack)
+}
+
+fn solve(dictionary: &Dictionary, options: &Options, rack: &str) -> Result<()> {
+    let sources = sources(dictionary, options)?;
+    let words = &dictionary.words;
+    let rack = parse_rack(dictionary, options, rack)?;
+    let Rack { letters, wildcards } = &rack;
+
+    let usable = usable(options, sources);
 
-            let entry = self.entries.entry(first).or_insert_with(WordTrie::new);
-            entry.add(&rest, value);
+    if options.phrases {
+        if !wildcards.is_empty() {
+            return Err(anyhow!("Wildcards can't be used when finding phrases"));
         }
+
+        let num_letters = letters.chars().count();
+        let limits = PhraseLimits {
+            letters: options.phrase_letters.unwrap_or(num_letters),
+            max_words: options.max_words,
+            min_len: options.min_len,
+        };
+        if limits.letters > num_letters {
+            return Err(anyhow!("Only {} letters to use", num_letters));
+        }
+
+        let mut phrases = phrases::find_phrases(words, letters, limits, &|entry| {
+            entry.words.iter().any(&usable)
+        });
+        phrases.sort_unstable_by_key(|phrase| {
+            (
+                phrase.len(),
+                phrase
+                    .iter()
+                    .map(|entry| &entry.alphagram)
+                    .collect::<Vec<_>>(),
+            )
+        });
+
+        for phrase in phrases {
+            let phrase = phrase
+                .iter()
+                .map(|entry| {
+                    entry
+                        .words
+                        .iter()
+                        .filter(|word| usable(word))
+                        .map(|word| label(word, dictionary))
+                        .collect::<Vec<_>>()
+                        .join("/")
+                })
+                .collect::<Vec<_>>();
+            println!("{}: {}", phrase.len(), phrase.join(" + "));
+        }
+
+        return Ok(());
     }
 
-    fn find_substrings(&self, letters: String) -> Vec<&T> {
-        let mut acc = vec![];
-        self.find_substrings_internal(letters, &mut acc);
-        acc
+    // With a query, an empty rack searches the whole dictionary
+    let substrings = if letters.is_empty() && wildcards.is_empty() && !options.query.is_empty() {
+        words.values().map(|entry| (entry, vec![])).collect()
+    } else {
+        rack.find(words)
+    };
+
+    let found: Vec<_> = substrings
+        .into_iter()
+        .filter_map(|(entry, blanks)| {
+            let words: Vec<&Word> = entry
+                .words
+                .iter()
+                .filter(|word| usable(word))
+                .filter(|word| {
+                    options
+                        .query
+                        .matches(&dictionary.alphabet.encode(&word.text))
+                })
+                .collect();
+            if words.is_empty() {
+                None
+            } else {
+                Some(Found {
+                    entry,
+                    words,
+                    blanks,
+                })
+            }
+        })
+        .collect();
+
+    // Rank by score, best first, or by length, shortest first, as it always has been
+    let mut ranked: Vec<_> = match &options.scoring {
+        Some(scoring) => {
+            let mut scored: Vec<_> = found
+                .into_iter()
+                .map(|found| {
+                    let blanks: Vec<char> = found.blanks.iter().flatten().copied().collect();
+                    (scoring.score(&found.entry.alphagram, &blanks), found)
+                })
+                .collect();
+            scored.sort_unstable_by(|(a_score, a), (b_score, b)| {
+                b_score
+                    .cmp(a_score)
+                    .then(b.entry.num_letters().cmp(&a.entry.num_letters()))
+                    .then(a.words.cmp(&b.words))
+            });
+            scored
+        }
+
+        None => {
+            let mut found: Vec<_> = found
+                .into_iter()
+                .map(|found| (found.entry.num_letters() as u32, found))
+                .collect();
+            found.sort_unstable_by(|(a_len, a), (b_len, b)| {
+                (a_len, &a.words).cmp(&(b_len, &b.words))
+            });
+            found
+        }
+    };
+
+    if let Some(top) = options.top {
+        ranked.truncate(top);
     }
 
-    fn find_substrings_internal(&'a self, letters: String, acc: &mut Vec<&'a T>) {
-        if let Some(ref value) = &self.value {
-            acc.push(value);
+    print_found(ranked, options, dictionary, wildcards);
+
+    Ok(())
+}
+
+fn print_found(
+    mut ranked: Vec<(u32, Found)>,
+    options: &Options,
+    dictionary: &Dictionary,
+    wildcards: &[Wildcard],
+) {
+    if options.alphabetical {
+        for (_, found) in ranked.iter_mut() {
+            found.words.sort_unstable_by_key(|word| &word.text);
         }
+        ranked.sort_by(|(_, a), (_, b)| a.words[0].text.cmp(&b.words[0].text));
+    }
 
-        if !letters.is_empty() {
-            let mut chars = letters.chars();
-            let first = chars.next().unwrap();
-            let rest: String = chars.collect();
+    match options.layout {
+        Layout::Joined => {
+            for (key, found) in ranked {
+                println!("{}: {}", key, found.describe(dictionary, wildcards));
+            }
+        }
 
-            if let Some(entry) = self.entries.get(&first) {
-                entry.find_substrings_internal(rest.clone(), acc);
+        Layout::OnePerLine => {
+            let mut lines: Vec<_> = ranked
+                .iter()
+                .flat_map(|(key, found)| {
+                    let blanks = found.describe_blanks(dictionary, wildcards);
+                    found
+                        .words
+                        .iter()
+                        .map(move |word| (key, *word, blanks.clone()))
+                })
+                .collect();
+            if options.alphabetical {
+                lines.sort_by_key(|(_, word, _)| &word.text);
+            }
+            for (key, word, blanks) in lines {
+                println!("{}: {}{}", key, label(word, dictionary), blanks);
             }
+        }
+
+        Layout::Grouped => {
+            for (key, found) in ranked {
+                println!(
+                    "{}: {}{}",
+                    key,
+                    dictionary.alphabet.decode(&found.entry.alphagram),
+                    found.describe_blanks(dictionary, wildcards)
+                );
+                for word in found.words {
+                    println!("  {}", label(word, dictionary));
+                }
+            }
+        }
+    }
+}
 
-            self.find_substrings_internal(rest, acc);
+// Shows which word lists accept a word, when more than one is loaded
+fn label(word: &Word, dictionary: &Dictionary) -> String {
+    if dictionary.num_lists() > 1 {
+        format!(
+            "{} [{}]",
+            word.text,
+            dictionary.names(word.sources).join(",")
+        )
+    } else {
+        word.text.clone()
+    }
+}
+
+// An entry found for a rack, with the words that passed any filters and the letters the rack's
+// wildcards stood for.
+struct Found<'a> {
+    entry: &'a Entry,
+    words: Vec<&'a Word>,
+    blanks: Vec<Option<char>>,
+}
+
+impl<'a> Found<'a> {
+    fn describe(&self, dictionary: &Dictionary, wildcards: &[Wildcard]) -> String {
+        let words = self
+            .words
+            .iter()
+            .map(|word| label(word, dictionary))
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{}{}", words, self.describe_blanks(dictionary, wildcards))
+    }
+
+    fn describe_blanks(&self, dictionary: &Dictionary, wildcards: &[Wildcard]) -> String {
+        let alphabet = &dictionary.alphabet;
+        let blanks = wildcards
+            .iter()
+            .zip(&self.blanks)
+            .filter_map(|(wildcard, c)| {
+                c.map(|c| {
+                    format!(
+                        "{}={}",
+                        alphabet.decode(&wildcard.to_string()),
+                        alphabet.decode_char(c)
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if blanks.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", blanks.join(", "))
         }
     }
 }
@@ -0,0 +1,88 @@
+use crate::dictionary::Entry;
+use crate::WordTrie;
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhraseLimits {
+    // How many of the rack's letters the phrase must use
+    pub letters: usize,
+    pub max_words: usize,
+    pub min_len: usize,
+}
+
+// Finds combinations of entries whose alphagrams together use exactly `limits.letters` of the
+// (sorted) letters. Each combination is listed once, with its entries in alphagram order.
+pub fn find_phrases<'a>(
+    words: &'a WordTrie<Entry>,
+    letters: &str,
+    limits: PhraseLimits,
+    usable: &dyn Fn(&Entry) -> bool,
+) -> Vec<Vec<&'a Entry>> {
+    let mut acc = vec![];
+    find_phrases_internal(words, letters, limits, usable, &mut vec![], &mut acc);
+    acc
+}
+
+fn find_phrases_internal<'a>(
+    words: &'a WordTrie<Entry>,
+    letters: &str,
+    limits: PhraseLimits,
+    usable: &dyn Fn(&Entry) -> bool,
+    phrase: &mut Vec<&'a Entry>,
+    acc: &mut Vec<Vec<&'a Entry>>,
+) {
+    let used: usize = phrase.iter().map(|entry| entry.num_letters()).sum();
+    let needed = limits.letters - used;
+    if needed == 0 {
+        acc.push(phrase.clone());
+        return;
+    }
+    if phrase.len() == limits.max_words {
+        return;
+    }
+
+    let mut seen = HashSet::new();
+    for entry in words.find_substrings(letters) {
+        let len = entry.num_letters();
+        if len < limits.min_len || len > needed || (len < needed && needed - len < limits.min_len) {
+            continue;
+        }
+
+        // Only build phrases in alphagram order so each combination turns up once
+        if phrase
+            .last()
+            .is_some_and(|last| entry.alphagram < last.alphagram)
+        {
+            continue;
+        }
+
+        if !seen.insert(&entry.alphagram) || !usable(entry) {
+            continue;
+        }
+
+        phrase.push(entry);
+        let rest = subtract(letters, &entry.alphagram);
+        find_phrases_internal(words, &rest, limits, usable, phrase, acc);
+        phrase.pop();
+    }
+}
+
+// Removes the letters of `alphagram` from `letters`, both sorted, where the first is known to be
+// a sub-multiset of the second.
+fn subtract(letters: &str, alphagram: &str) -> String {
+    let mut alphagram = alphagram.chars().peekable();
+    letters
+        .chars()
+        .filter(|&c| {
+            if alphagram.peek() == Some(&c) {
+                alphagram.next();
+                false
+            } else {
+                true
+            }
+        })
+        .collect()
+}
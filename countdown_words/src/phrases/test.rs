@@ -0,0 +1,92 @@
+use super::*;
+use crate::dictionary::Dictionary;
+
+fn dictionary() -> Dictionary {
+    let mut dictionary = Dictionary::new();
+    dictionary
+        .read(
+            "test",
+            "ACT,CAT\nEST,SET\nAT,AT\nCEST,SECT\nAEST,EATS\n".as_bytes(),
+        )
+        .unwrap();
+    dictionary
+}
+
+fn phrases(dictionary: &Dictionary, letters: &str, limits: PhraseLimits) -> Vec<String> {
+    find_phrases(&dictionary.words, letters, limits, &|_| true)
+        .into_iter()
+        .map(|phrase| {
+            let words: Vec<_> = phrase
+                .iter()
+                .map(|entry| entry.words[0].text.as_str())
+                .collect();
+            words.join(" ")
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_phrases() {
+    let dictionary = dictionary();
+    let limits = PhraseLimits {
+        letters: 6,
+        max_words: 3,
+        min_len: 2,
+    };
+
+    // Each combination once, in alphagram order, rather than once per order of its words
+    let mut found = phrases(&dictionary, "ACESTT", limits);
+    found.sort_unstable();
+    assert_eq!(found, vec!["AT SECT", "CAT SET"]);
+
+    let found = phrases(
+        &dictionary,
+        "ACESTT",
+        PhraseLimits {
+            min_len: 3,
+            ..limits
+        },
+    );
+    assert_eq!(found, vec!["CAT SET"]);
+
+    let found = phrases(
+        &dictionary,
+        "ACESTT",
+        PhraseLimits {
+            max_words: 1,
+            ..limits
+        },
+    );
+    assert!(found.is_empty());
+}
+
+#[test]
+fn test_use_fewer_letters() {
+    let dictionary = dictionary();
+    let limits = PhraseLimits {
+        letters: 4,
+        max_words: 3,
+        min_len: 2,
+    };
+
+    let mut found = phrases(&dictionary, "ACESTT", limits);
+    found.sort_unstable();
+    assert_eq!(found, vec!["EATS", "SECT"]);
+
+    let found = phrases(
+        &dictionary,
+        "ACESTT",
+        PhraseLimits {
+            letters: 5,
+            ..limits
+        },
+    );
+    assert_eq!(found, vec!["AT SET"]);
+}
+
+#[test]
+fn test_subtract() {
+    assert_eq!(subtract("ACESTT", "ACT"), "EST");
+    assert_eq!(subtract("AAT", "AT"), "A");
+    assert_eq!(subtract("ACT", "ACT"), "");
+}
@@ -0,0 +1,156 @@
+use crate::error::InputError;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Letter(char),
+    Any,
+    Class(Vec<char>),
+    NotClass(Vec<char>),
+    Run,
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Letter(l) => *l == c,
+            Token::Any => true,
+            Token::Class(class) => class.contains(&c),
+            Token::NotClass(class) => !class.contains(&c),
+            Token::Run => unreachable!(),
+        }
+    }
+}
+
+// A crossword-style pattern: letters match themselves, '.' or '?' match any one letter, "[ABC]"
+// and "[^ABC]" match one letter in or not in a set, and '*' matches any run of letters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern(Vec<Token>);
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> Result<Self, InputError> {
+        let mut tokens = vec![];
+
+        let mut chars = pattern.chars().map(|c| c.to_ascii_uppercase());
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '.' | '?' => Token::Any,
+                '*' => Token::Run,
+                '[' => {
+                    let mut class = vec![];
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => class.push(c),
+                            None => return Err(InputError::UnterminatedClass(pattern.to_owned())),
+                        }
+                    }
+                    let negated = class.first() == Some(&'^');
+                    if negated {
+                        class.remove(0);
+                    }
+                    if class.is_empty() {
+                        return Err(InputError::EmptyClass);
+                    }
+                    if negated {
+                        Token::NotClass(class)
+                    } else {
+                        Token::Class(class)
+                    }
+                }
+                _ => Token::Letter(c),
+            });
+        }
+
+        Ok(Self(tokens))
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let word: Vec<char> = word.chars().collect();
+        Self::matches_internal(&self.0, &word)
+    }
+
+    fn matches_internal(tokens: &[Token], word: &[char]) -> bool {
+        match tokens.split_first() {
+            None => word.is_empty(),
+            Some((Token::Run, rest)) => {
+                (0..=word.len()).any(|i| Self::matches_internal(rest, &word[i..]))
+            }
+            Some((token, rest)) => match word.split_first() {
+                Some((&c, word)) => token.matches(c) && Self::matches_internal(rest, word),
+                None => false,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Query {
+    pub pattern: Option<Pattern>,
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    pub required: Vec<char>,
+    pub excluded: Vec<char>,
+}
+
+impl Query {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // Accepts a single length such as "7", or a range as taken by parse_range
+    pub fn set_length(&mut self, length: &str) -> Result<(), InputError> {
+        let (min_len, max_len) =
+            parse_range(length).ok_or_else(|| InputError::BadLength(length.to_owned()))?;
+        self.min_len = min_len;
+        self.max_len = max_len;
+        Ok(())
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        if self.min_len.is_some_and(|min_len| len < min_len)
+            || self.max_len.is_some_and(|max_len| len > max_len)
+        {
+            return false;
+        }
+
+        if word.chars().any(|c| self.excluded.contains(&c)) {
+            return false;
+        }
+
+        // Required letters are a multiset, so "EE" needs two Es
+        let mut letters: Vec<char> = word.chars().collect();
+        for c in &self.required {
+            match letters.iter().position(|l| l == c) {
+                Some(i) => {
+                    letters.swap_remove(i);
+                }
+                None => return false,
+            }
+        }
+
+        self.pattern
+            .as_ref()
+            .is_none_or(|pattern| pattern.matches(word))
+    }
+}
+
+// Parses a single number such as "7", or an inclusive range such as "5-7", "5-" or "-7", into its
+// optional bounds
+pub fn parse_range(range: &str) -> Option<(Option<usize>, Option<usize>)> {
+    let parse = |s: &str| -> Option<Option<usize>> {
+        if s.is_empty() {
+            Some(None)
+        } else {
+            s.parse().ok().map(Some)
+        }
+    };
+
+    match range.split_once('-') {
+        Some((min, max)) => Some((parse(min)?, parse(max)?)),
+        None => Some((parse(range)?, parse(range)?)),
+    }
+}
@@ -0,0 +1,96 @@
+use super::*;
+use anyhow::Result;
+
+fn matches(pattern: &str, word: &str) -> bool {
+    Pattern::parse(pattern).unwrap().matches(word)
+}
+
+#[test]
+fn test_pattern() {
+    assert!(matches("c.t", "CAT"));
+    assert!(matches("C?T", "COT"));
+    assert!(!matches("c.t", "CART"));
+    assert!(!matches("c.t", "CT"));
+
+    // Runs may be empty, and may appear anywhere
+    assert!(matches("c*", "C"));
+    assert!(matches("c*t", "CAT"));
+    assert!(matches("c*t", "CT"));
+    assert!(matches("*at*", "CRATES"));
+    assert!(matches("*", ""));
+    assert!(!matches("c*t", "CATS"));
+
+    assert!(matches("[bc]at", "CAT"));
+    assert!(!matches("[bc]at", "HAT"));
+    assert!(matches("[^bc]at", "HAT"));
+    assert!(!matches("[^bc]at", "BAT"));
+    assert!(matches("*[aeiou][aeiou]*", "GOATEES"));
+    assert!(!matches("*[aeiou][aeiou]*", "CRATES"));
+}
+
+#[test]
+fn test_pattern_errors() {
+    assert_eq!(
+        Pattern::parse("c[at"),
+        Err(InputError::UnterminatedClass("c[at".to_string()))
+    );
+    assert_eq!(Pattern::parse("c[]t"), Err(InputError::EmptyClass));
+    assert_eq!(Pattern::parse("c[^]t"), Err(InputError::EmptyClass));
+}
+
+#[test]
+fn test_letters() {
+    let query = Query {
+        required: vec!['E', 'E'],
+        excluded: vec!['S'],
+        ..Query::default()
+    };
+    // Required letters count, so one E isn't enough
+    assert!(query.matches("GOATEE"));
+    assert!(!query.matches("CRATE"));
+    assert!(!query.matches("GOATEES"));
+    assert!(!query.is_empty());
+    assert!(Query::default().is_empty());
+}
+
+#[test]
+fn test_length() -> Result<()> {
+    let mut query = Query::default();
+
+    query.set_length("5")?;
+    assert_eq!((query.min_len, query.max_len), (Some(5), Some(5)));
+    assert!(query.matches("CRATE"));
+    assert!(!query.matches("CRATES"));
+
+    query.set_length("5-7")?;
+    assert_eq!((query.min_len, query.max_len), (Some(5), Some(7)));
+    assert!(query.matches("CRATES"));
+    assert!(!query.matches("CAT"));
+
+    query.set_length("-7")?;
+    assert_eq!((query.min_len, query.max_len), (None, Some(7)));
+    assert!(query.matches("AT"));
+    assert!(!query.matches("GRANITES"));
+
+    query.set_length("5-")?;
+    assert_eq!((query.min_len, query.max_len), (Some(5), None));
+
+    assert_eq!(
+        query.set_length("bad"),
+        Err(InputError::BadLength("bad".to_string()))
+    );
+    assert!(query.set_length("5-x").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_range() {
+    assert_eq!(parse_range("3"), Some((Some(3), Some(3))));
+    assert_eq!(parse_range("3-5"), Some((Some(3), Some(5))));
+    assert_eq!(parse_range("-5"), Some((None, Some(5))));
+    assert_eq!(parse_range("3-"), Some((Some(3), None)));
+    assert_eq!(parse_range("-"), Some((None, None)));
+    assert_eq!(parse_range("x"), None);
+    assert_eq!(parse_range("3-x"), None);
+}
@@ -0,0 +1,108 @@
+use crate::alphabet::Alphabet;
+use crate::dictionary::make_alphagram;
+use crate::error::InputError;
+use crate::{Wildcard, WordTrie};
+use std::collections::HashSet;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rack {
+    // Sorted, so it can be searched for directly in an alphagram trie
+    pub letters: String,
+    pub wildcards: Vec<Wildcard>,
+}
+
+impl Rack {
+    // Parses a rack such as "abc?[aeiou]", upper-casing it and splitting its literal letters from
+    // its wildcards. Anything else that isn't a letter is rejected.
+    pub fn parse(rack: &str) -> Result<Self, InputError> {
+        Self::parse_in(rack, &Alphabet::new())
+    }
+
+    // Like parse, but encodes the letters into tiles with the given alphabet
+    pub fn parse_in(rack: &str, alphabet: &Alphabet) -> Result<Self, InputError> {
+        let rack = alphabet.encode(rack);
+
+        let mut letters = vec![];
+        let mut wildcards = vec![];
+
+        let mut chars = rack.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '?' => wildcards.push(Wildcard::Any),
+                '[' => {
+                    let mut class = vec![];
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) if alphabet.is_letter(c) => class.push(c),
+                            Some(c) => return Err(InputError::BadLetter(alphabet.decode_char(c))),
+                            None => {
+                                return Err(InputError::UnterminatedClass(alphabet.decode(&rack)))
+                            }
+                        }
+                    }
+                    if class.is_empty() {
+                        return Err(InputError::EmptyClass);
+                    }
+                    class.sort_unstable();
+                    class.dedup();
+                    wildcards.push(Wildcard::Class(class));
+                }
+                _ if c.is_whitespace() => {}
+                _ if alphabet.is_letter(c) => letters.push(c),
+                _ => return Err(InputError::BadLetter(alphabet.decode_char(c))),
+            }
+        }
+
+        letters.sort_unstable();
+        Ok(Self {
+            letters: letters.into_iter().collect(),
+            wildcards,
+        })
+    }
+
+    // The number of tiles in the rack, counting each wildcard as one
+    pub fn len(&self) -> usize {
+        self.letters.chars().count() + self.wildcards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty() && self.wildcards.is_empty()
+    }
+
+    pub fn check_len(&self, max_len: usize) -> Result<(), InputError> {
+        let len = self.len();
+        if len > max_len {
+            Err(InputError::TooManyLetters { len, max_len })
+        } else {
+            Ok(())
+        }
+    }
+
+    // Finds every word in an alphagram trie that can be made from the rack, once each, with the
+    // letters any wildcards stood for
+    pub fn find<'a, T>(&self, words: &'a WordTrie<T>) -> Vec<(&'a T, Vec<Option<char>>)> {
+        if self.wildcards.is_empty() {
+            let mut seen = HashSet::new();
+            words
+                .find_substrings(&self.letters)
+                .into_iter()
+                .filter(|value| seen.insert(*value as *const T))
+                .map(|value| (value, vec![]))
+                .collect()
+        } else {
+            words.find_with_wildcards(&self.letters, &self.wildcards)
+        }
+    }
+
+    // The letters the wildcards would stand for if the word, already encoded in the same alphabet
+    // as the rack, were made from the rack, if it can be
+    pub fn make(&self, word: &str) -> Option<Vec<Option<char>>> {
+        let mut words = WordTrie::new();
+        words.add(&make_alphagram(word), ());
+        self.find(&words).pop().map(|(_, blanks)| blanks)
+    }
+}
@@ -0,0 +1,95 @@
+use super::*;
+use crate::WordTrie;
+use anyhow::Result;
+
+#[test]
+fn test_parse() -> Result<()> {
+    assert_eq!(
+        Rack::parse("CRATES")?,
+        Rack {
+            letters: "ACERST".to_string(),
+            wildcards: vec![]
+        }
+    );
+
+    assert_eq!(
+        Rack::parse(" c r?ate[uoi] \n")?,
+        Rack {
+            letters: "ACERT".to_string(),
+            wildcards: vec![Wildcard::Any, Wildcard::Class(vec!['I', 'O', 'U'])]
+        }
+    );
+
+    assert_eq!(Rack::parse("")?.letters, "");
+    assert!(Rack::parse("AB[CD").is_err());
+    assert!(Rack::parse("AB[]").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_invalid() -> Result<()> {
+    assert_eq!(
+        Rack::parse("AB1C"),
+        Err(InputError::BadLetter("1".to_string()))
+    );
+    assert_eq!(
+        Rack::parse("AB[C-]"),
+        Err(InputError::BadLetter("-".to_string()))
+    );
+    assert_eq!(Rack::parse("AB[]"), Err(InputError::EmptyClass));
+    assert_eq!(Rack::parse("ÉTÉ")?.letters, "EET");
+
+    let rack = Rack::parse("CRATE??")?;
+    assert_eq!(rack.len(), 7);
+    assert_eq!(rack.check_len(7), Ok(()));
+    assert_eq!(
+        rack.check_len(6),
+        Err(InputError::TooManyLetters { len: 7, max_len: 6 })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_lower_case_search() -> Result<()> {
+    let mut trie = WordTrie::new();
+    trie.add("ACT", "CAT");
+    trie.add("AT", "AT");
+
+    let mut found = trie.find_substrings(&Rack::parse("tacky")?.letters);
+    found.sort_unstable();
+    found.dedup();
+    assert_eq!(found, vec![&"AT", &"CAT"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_find_with_wildcards() -> Result<()> {
+    let mut trie = WordTrie::new();
+    for word in ["ACT", "ACST", "AT", "EST"] {
+        trie.add(word, word);
+    }
+
+    let mut found = Rack::parse("AT?")?.find(&trie);
+    found.sort_unstable();
+    assert_eq!(found, vec![(&"ACT", vec![Some('C')]), (&"AT", vec![None])]);
+
+    // A class only stands for its own letters
+    assert!(Rack::parse("T[ES]")?.find(&trie).is_empty());
+    let mut found = Rack::parse("ST[AE]")?.find(&trie);
+    found.sort_unstable();
+    assert_eq!(
+        found,
+        vec![(&"AT", vec![Some('A')]), (&"EST", vec![Some('E')])]
+    );
+
+    // Letters in the rack are used before wildcards
+    let rack = Rack::parse("CAT??")?;
+    assert_eq!(rack.make("ACST"), Some(vec![Some('S'), None]));
+    assert_eq!(rack.make("ACT"), Some(vec![None, None]));
+    assert_eq!(rack.make("AACCTT"), None);
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::alphabet::Alphabet;
+use crate::error::{Error, InputError, LoadError};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{self, BufReader};
+
+#[cfg(test)]
+mod test;
+
+const SCRABBLE_VALUES: [u32; 26] = [
+    1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10,
+];
+
+const WORDS_WITH_FRIENDS_VALUES: [u32; 26] = [
+    1, 4, 4, 2, 1, 4, 3, 3, 1, 10, 5, 2, 4, 2, 1, 4, 10, 1, 1, 1, 2, 5, 4, 8, 3, 10,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Scoring {
+    // One point per letter, except that a nine scores double
+    Countdown,
+    Scrabble,
+    WordsWithFriends,
+    // Keyed by encoded letter, so multi-letter tiles are single chars
+    Custom(HashMap<char, u32>),
+}
+
+impl Scoring {
+    // Accepts the name of a built-in scheme, or the path to a CSV file of "LETTER,VALUE" lines.
+    // The built-in letter values are only for plain A to Z.
+    pub fn parse(scheme: &str, alphabet: &Alphabet) -> Result<Self, Error> {
+        let scoring = match scheme.to_ascii_lowercase().as_str() {
+            "countdown" | "length" => return Ok(Scoring::Countdown),
+            "scrabble" => Scoring::Scrabble,
+            "wwf" | "words-with-friends" => Scoring::WordsWithFriends,
+            _ => return Self::load(scheme, alphabet),
+        };
+
+        if !alphabet.is_plain() {
+            return Err(InputError::NoLetterValues(scheme.to_owned()).into());
+        }
+        Ok(scoring)
+    }
+
+    fn load(path: &str, alphabet: &Alphabet) -> Result<Self, Error> {
+        let f = match File::open(path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(InputError::UnknownScoring(path.to_owned()).into())
+            }
+            Err(e) => return Err(LoadError::Io(e).into()),
+        };
+        Ok(Self::read(BufReader::new(f), alphabet)?)
+    }
+
+    // Reads "LETTER,VALUE" lines, where a letter may be a multi-letter tile of the alphabet
+    pub fn read(reader: impl BufRead, alphabet: &Alphabet) -> Result<Self, LoadError> {
+        let mut values = HashMap::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad_line = || LoadError::BadValuesLine {
+                line_number: i + 1,
+                line: line.to_owned(),
+            };
+
+            let (letter, value) = line.split_once(',').ok_or_else(bad_line)?;
+            let letter = alphabet.encode(letter);
+            let mut letters = letter.chars();
+            let letter = match (letters.next(), letters.next()) {
+                (Some(c), None) if alphabet.is_letter(c) => c,
+                _ => return Err(bad_line()),
+            };
+            values.insert(letter, value.trim().parse().map_err(|_| bad_line())?);
+        }
+
+        Ok(Scoring::Custom(values))
+    }
+
+    // Scores the letters of a word, where the letters in `blanks` were played as blank tiles
+    pub fn score(&self, letters: &str, blanks: &[char]) -> u32 {
+        match self {
+            Scoring::Countdown => match letters.chars().count() {
+                9 => 18,
+                len => len as u32,
+            },
+            _ => {
+                let total: u32 = letters.chars().map(|c| self.letter_value(c)).sum();
+                let blanks: u32 = blanks.iter().map(|&c| self.letter_value(c)).sum();
+                total - blanks
+            }
+        }
+    }
+
+    fn letter_value(&self, c: char) -> u32 {
+        let table = match self {
+            Scoring::Countdown => return 1,
+            Scoring::Scrabble => &SCRABBLE_VALUES,
+            Scoring::WordsWithFriends => &WORDS_WITH_FRIENDS_VALUES,
+            Scoring::Custom(values) => return values.get(&c).copied().unwrap_or(0),
+        };
+
+        if c.is_ascii_uppercase() {
+            table[(c as u8 - b'A') as usize]
+        } else {
+            0
+        }
+    }
+}
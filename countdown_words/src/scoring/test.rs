@@ -0,0 +1,78 @@
+use super::*;
+use anyhow::Result;
+
+#[test]
+fn test_countdown() {
+    let scoring = Scoring::Countdown;
+    assert_eq!(scoring.score("ACT", &[]), 3);
+    assert_eq!(scoring.score("ACEGIORST", &[]), 18);
+    assert_eq!(scoring.score("AACEGIORST", &[]), 10);
+    // Blanks still count towards the length
+    assert_eq!(scoring.score("ACEGIORST", &['A']), 18);
+}
+
+#[test]
+fn test_letter_values() {
+    assert_eq!(Scoring::Scrabble.score("AEQZ", &[]), 22);
+    assert_eq!(Scoring::WordsWithFriends.score("AEQZ", &[]), 22);
+    assert_eq!(Scoring::Scrabble.score("CJK", &[]), 16);
+    assert_eq!(Scoring::WordsWithFriends.score("CJK", &[]), 19);
+
+    // A letter played as a blank scores nothing
+    assert_eq!(Scoring::Scrabble.score("AEQZ", &['Z']), 12);
+    assert_eq!(Scoring::Scrabble.score("AEQZ", &['Q', 'Z']), 2);
+}
+
+#[test]
+fn test_custom_file() -> Result<()> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/values.csv");
+    let scoring = Scoring::parse(path, &Alphabet::new())?;
+    assert_eq!(scoring.score("AAB", &[]), 9);
+    assert_eq!(scoring.score("ABZ", &['B']), 2);
+    // Letters the file doesn't mention score nothing
+    assert_eq!(scoring.score("CAT", &[]), 2);
+
+    assert!(matches!(
+        Scoring::parse("no-such-scoring", &Alphabet::new()),
+        Err(Error::Input(InputError::UnknownScoring(_)))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed() {
+    let read = |contents: &str| Scoring::read(contents.as_bytes(), &Alphabet::new());
+    for contents in ["A\n", "A,one\n", "AB,2\n", ",2\n", "1,2\n"] {
+        assert!(read(contents).is_err(), "{:?} should be rejected", contents);
+    }
+
+    let err = read("A,1\n\nB;3\n").unwrap_err();
+    assert_eq!(err.to_string(), "Bad letter values line 3: B;3");
+    assert!(matches!(
+        err,
+        LoadError::BadValuesLine { line_number: 3, .. }
+    ));
+}
+
+#[test]
+fn test_custom_alphabet() -> Result<()> {
+    let alphabet = Alphabet::parse("spanish")?;
+    let scoring = Scoring::read("ñ,8\nLL,8\nA,1\nR,1\nO,1\n".as_bytes(), &alphabet)?;
+
+    // Values are keyed the way words are encoded, so accents and tiles match
+    assert_eq!(scoring.score(&alphabet.alphagram("año"), &[]), 10);
+    assert_eq!(scoring.score(&alphabet.alphagram("llorar"), &[]), 12);
+
+    // Letters outside the alphabet can't be given values
+    assert!(Scoring::read("ж,8\n".as_bytes(), &alphabet).is_err());
+
+    // The built-in values are only for A to Z
+    assert!(matches!(
+        Scoring::parse("scrabble", &alphabet),
+        Err(Error::Input(InputError::NoLetterValues(_)))
+    ));
+    assert_eq!(Scoring::parse("length", &alphabet)?, Scoring::Countdown);
+
+    Ok(())
+}
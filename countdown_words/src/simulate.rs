@@ -0,0 +1,149 @@
+use crate::dictionary::{make_alphagram, Entry, Word};
+use crate::WordTrie;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod test;
+
+// The tiles in the Countdown vowel and consonant piles
+const VOWELS: [(char, usize); 5] = [('A', 15), ('E', 21), ('I', 13), ('O', 13), ('U', 5)];
+const CONSONANTS: [(char, usize); 21] = [
+    ('B', 2),
+    ('C', 3),
+    ('D', 6),
+    ('F', 2),
+    ('G', 3),
+    ('H', 2),
+    ('J', 1),
+    ('K', 1),
+    ('L', 5),
+    ('M', 4),
+    ('N', 8),
+    ('P', 4),
+    ('Q', 1),
+    ('R', 9),
+    ('S', 9),
+    ('T', 9),
+    ('V', 1),
+    ('W', 1),
+    ('X', 1),
+    ('Y', 1),
+    ('Z', 1),
+];
+
+pub const RACK_SIZE: usize = 9;
+
+fn pile(tiles: &[(char, usize)]) -> Vec<char> {
+    tiles
+        .iter()
+        .flat_map(|&(c, count)| std::iter::repeat_n(c, count))
+        .collect()
+}
+
+// Draws a rack of `RACK_SIZE` letters with the given number of vowels, without replacement from
+// full piles, returned as an alphagram
+pub fn draw(rng: &mut impl Rng, vowels: usize) -> String {
+    assert!(vowels <= RACK_SIZE);
+
+    let vowels = pile(&VOWELS)
+        .choose_multiple(rng, vowels)
+        .copied()
+        .collect::<Vec<_>>();
+    let consonants = pile(&CONSONANTS)
+        .choose_multiple(rng, RACK_SIZE - vowels.len())
+        .copied()
+        .collect::<Vec<_>>();
+
+    make_alphagram(&vowels.into_iter().chain(consonants).collect::<String>())
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub racks: usize,
+    // Indexed by the length of the longest word found, zero if there were none
+    pub max_lens: Vec<usize>,
+    // How many racks each word was (one of) the longest for
+    pub best_words: HashMap<String, usize>,
+}
+
+impl Stats {
+    pub fn add(&mut self, best: &[&Word]) {
+        let max_len = best.first().map_or(0, |word| word.text.chars().count());
+        if self.max_lens.len() <= max_len {
+            self.max_lens.resize(max_len + 1, 0);
+        }
+
+        self.racks += 1;
+        self.max_lens[max_len] += 1;
+        for word in best {
+            *self.best_words.entry(word.text.clone()).or_default() += 1;
+        }
+    }
+
+    pub fn nines(&self) -> usize {
+        self.max_lens.get(RACK_SIZE).copied().unwrap_or(0)
+    }
+
+    // The most frequent best words, most frequent first
+    pub fn top_words(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut words: Vec<_> = self
+            .best_words
+            .iter()
+            .map(|(word, &count)| (word.as_str(), count))
+            .collect();
+        words.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        words.truncate(n);
+        words
+    }
+}
+
+// The longest usable words that can be made from the letters
+pub fn best_words<'a>(
+    words: &'a WordTrie<Entry>,
+    letters: &str,
+    usable: &dyn Fn(&Word) -> bool,
+) -> Vec<&'a Word> {
+    let mut best: Vec<&Word> = vec![];
+    for entry in words.find_substrings(letters) {
+        let len = entry.num_letters();
+        if best
+            .first()
+            .is_some_and(|word| word.text.chars().count() > len)
+        {
+            continue;
+        }
+
+        let mut usable: Vec<_> = entry.words.iter().filter(|word| usable(word)).collect();
+        if usable.is_empty() {
+            continue;
+        }
+        if best
+            .first()
+            .is_some_and(|word| word.text.chars().count() < len)
+        {
+            best.clear();
+        }
+        best.append(&mut usable);
+    }
+
+    best.sort_unstable_by_key(|word| &word.text);
+    best.dedup_by_key(|word| &word.text);
+    best
+}
+
+pub fn simulate(
+    words: &WordTrie<Entry>,
+    rng: &mut impl Rng,
+    vowels: usize,
+    racks: usize,
+    usable: &dyn Fn(&Word) -> bool,
+) -> Stats {
+    let mut stats = Stats::default();
+    for _ in 0..racks {
+        let letters = draw(rng, vowels);
+        stats.add(&best_words(words, &letters, usable));
+    }
+    stats
+}
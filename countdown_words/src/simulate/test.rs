@@ -0,0 +1,57 @@
+use super::*;
+use crate::dictionary::Dictionary;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_draw() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for vowels in 0..=RACK_SIZE {
+        let rack = draw(&mut rng, vowels);
+        assert_eq!(rack.len(), RACK_SIZE);
+        assert_eq!(
+            rack.chars().filter(|c| "AEIOU".contains(*c)).count(),
+            vowels
+        );
+        assert_eq!(make_alphagram(&rack), rack);
+    }
+}
+
+#[test]
+fn test_best_words() {
+    let mut dictionary = Dictionary::new();
+    dictionary
+        .read("test", "ACT,CAT/ACT\nAT,AT\nAST,SAT*\nEST,SET\n".as_bytes())
+        .unwrap();
+
+    let best = best_words(&dictionary.words, "ACEST", &|_| true);
+    let best: Vec<_> = best.iter().map(|word| word.text.as_str()).collect();
+    assert_eq!(best, vec!["ACT", "CAT", "SAT", "SET"]);
+
+    let best = best_words(&dictionary.words, "AST", &|word| !word.obscure);
+    let best: Vec<_> = best.iter().map(|word| word.text.as_str()).collect();
+    assert_eq!(best, vec!["AT"]);
+
+    assert!(best_words(&dictionary.words, "XYZ", &|_| true).is_empty());
+}
+
+#[test]
+fn test_stats() {
+    let word = |text: &str| Word {
+        text: text.to_string(),
+        obscure: false,
+        sources: 1,
+    };
+    let (cat, act, sat) = (word("CAT"), word("ACT"), word("SAT"));
+
+    let mut stats = Stats::default();
+    stats.add(&[&act, &cat]);
+    stats.add(&[&cat]);
+    stats.add(&[]);
+    stats.add(&[&sat]);
+
+    assert_eq!(stats.racks, 4);
+    assert_eq!(stats.max_lens, vec![1, 0, 0, 3]);
+    assert_eq!(stats.nines(), 0);
+    assert_eq!(stats.top_words(2), vec![("CAT", 2), ("ACT", 1)]);
+}
@@ -0,0 +1,17 @@
+use crate::dictionary::{Dictionary, Word};
+
+// The small word list in testdata, loaded as "small"
+pub(crate) fn fixture() -> Dictionary {
+    let mut dictionary = Dictionary::new();
+    dictionary
+        .load(
+            "small",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/small.csv"),
+        )
+        .unwrap();
+    dictionary
+}
+
+pub(crate) fn texts(words: &[&Word]) -> Vec<String> {
+    words.iter().map(|word| word.text.clone()).collect()
+}
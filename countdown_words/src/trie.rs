@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Wildcard {
+    Any,
+    Class(Vec<char>),
+}
+
+impl Wildcard {
+    pub fn matches(&self, c: char) -> bool {
+        match self {
+            Wildcard::Any => true,
+            Wildcard::Class(class) => class.contains(&c),
+        }
+    }
+}
+
+impl fmt::Display for Wildcard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wildcard::Any => write!(f, "?"),
+            Wildcard::Class(class) => write!(f, "[{}]", class.iter().collect::<String>()),
+        }
+    }
+}
+
+// A trie keyed by strings. Keys can be anything, but the substring searches expect each key to be
+// an alphagram, i.e. its letters in sorted order, and so find the keys that can be made from a
+// multiset of letters.
+#[derive(Clone, Debug)]
+pub struct WordTrie<T> {
+    entries: BTreeMap<char, WordTrie<T>>,
+    value: Option<T>,
+}
+
+impl<T> Default for WordTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> WordTrie<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            value: None,
+        }
+    }
+
+    // Sets the value for a word, returning the value it replaced
+    pub fn add(&mut self, word: &str, value: T) -> Option<T> {
+        let mut chars = word.chars();
+        match chars.next() {
+            None => self.value.replace(value),
+            Some(first) => self
+                .entries
+                .entry(first)
+                .or_default()
+                .add(chars.as_str(), value),
+        }
+    }
+
+    pub fn get(&self, word: &str) -> Option<&T> {
+        let mut chars = word.chars();
+        match chars.next() {
+            None => self.value.as_ref(),
+            Some(first) => self.entries.get(&first)?.get(chars.as_str()),
+        }
+    }
+
+    pub fn get_mut(&mut self, word: &str) -> Option<&mut T> {
+        let mut chars = word.chars();
+        match chars.next() {
+            None => self.value.as_mut(),
+            Some(first) => self.entries.get_mut(&first)?.get_mut(chars.as_str()),
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.get(word).is_some()
+    }
+
+    // Removes a word, pruning any branches left without values
+    pub fn remove(&mut self, word: &str) -> Option<T> {
+        let mut chars = word.chars();
+        match chars.next() {
+            None => self.value.take(),
+            Some(first) => {
+                let entry = self.entries.get_mut(&first)?;
+                let value = entry.remove(chars.as_str());
+                if entry.is_empty() {
+                    self.entries.remove(&first);
+                }
+                value
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.entries.is_empty()
+    }
+
+    pub fn count(&self) -> usize {
+        self.entries.values().map(WordTrie::count).sum::<usize>()
+            + usize::from(self.value.is_some())
+    }
+
+    // Every word and its value, in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: vec![(String::new(), self)],
+        }
+    }
+
+    // Every word starting with the prefix and its value, in sorted order
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'_, T> {
+        let mut node = self;
+        for c in prefix.chars() {
+            match node.entries.get(&c) {
+                Some(entry) => node = entry,
+                None => return Iter { stack: vec![] },
+            }
+        }
+
+        Iter {
+            stack: vec![(prefix.to_owned(), node)],
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    // Finds the values of all the words that can be made from the (sorted) letters. A value is
+    // found once for each way of skipping the letters it doesn't use, so callers should dedup.
+    pub fn find_substrings(&self, letters: &str) -> Vec<&T> {
+        let mut acc = vec![];
+        self.find_substrings_internal(letters, &mut acc);
+        acc
+    }
+
+    fn find_substrings_internal<'a>(&'a self, letters: &str, acc: &mut Vec<&'a T>) {
+        if let Some(ref value) = &self.value {
+            acc.push(value);
+        }
+
+        let mut chars = letters.chars();
+        if let Some(first) = chars.next() {
+            let rest = chars.as_str();
+
+            if let Some(entry) = self.entries.get(&first) {
+                entry.find_substrings_internal(rest, acc);
+            }
+
+            self.find_substrings_internal(rest, acc);
+        }
+    }
+
+    // Like find_substrings, but each wildcard may stand for any one letter it matches. Each result
+    // comes with the letter each wildcard stood for, or None if it wasn't needed.
+    pub fn find_with_wildcards(
+        &self,
+        letters: &str,
+        wildcards: &[Wildcard],
+    ) -> Vec<(&T, Vec<Option<char>>)> {
+        let mut letters: Vec<char> = letters.chars().collect();
+        let mut blanks = vec![None; wildcards.len()];
+        let mut acc = vec![];
+        self.find_with_wildcards_internal(&mut letters, wildcards, &mut blanks, &mut acc);
+
+        // Different wildcards can reach the same word, only keep the first way found
+        let mut seen = HashSet::new();
+        acc.retain(|(value, _)| seen.insert(*value as *const T));
+        acc
+    }
+
+    fn find_with_wildcards_internal<'a>(
+        &'a self,
+        letters: &mut Vec<char>,
+        wildcards: &[Wildcard],
+        blanks: &mut Vec<Option<char>>,
+        acc: &mut Vec<(&'a T, Vec<Option<char>>)>,
+    ) {
+        if let Some(ref value) = &self.value {
+            acc.push((value, blanks.clone()));
+        }
+
+        for (&c, entry) in &self.entries {
+            // Always prefer a real letter over a wildcard, it can never lead to fewer words
+            if let Some(i) = letters.iter().position(|&l| l == c) {
+                letters.remove(i);
+                entry.find_with_wildcards_internal(letters, wildcards, blanks, acc);
+                letters.insert(i, c);
+                continue;
+            }
+
+            for (i, wildcard) in wildcards.iter().enumerate() {
+                let is_duplicate = wildcards[..i]
+                    .iter()
+                    .zip(blanks.iter())
+                    .any(|(w, b)| b.is_none() && w == wildcard);
+                if blanks[i].is_some() || is_duplicate || !wildcard.matches(c) {
+                    continue;
+                }
+
+                blanks[i] = Some(c);
+                entry.find_with_wildcards_internal(letters, wildcards, blanks, acc);
+                blanks[i] = None;
+            }
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    stack: Vec<(String, &'a WordTrie<T>)>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (String, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((word, node)) = self.stack.pop() {
+            for (&c, entry) in node.entries.iter().rev() {
+                let mut word = word.clone();
+                word.push(c);
+                self.stack.push((word, entry));
+            }
+
+            if let Some(value) = &node.value {
+                return Some((word, value));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T> IntoIterator for &'a WordTrie<T> {
+    type Item = (String, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+fn trie(words: &[&str]) -> WordTrie<String> {
+    let mut trie = WordTrie::new();
+    for word in words {
+        trie.add(word, word.to_string());
+    }
+    trie
+}
+
+#[test]
+fn test_add_get() {
+    let mut trie = trie(&["ACT", "ACTS", "AT"]);
+    assert_eq!(trie.get("ACT"), Some(&"ACT".to_string()));
+    assert_eq!(trie.get("AT"), Some(&"AT".to_string()));
+    assert_eq!(trie.get("AC"), None);
+    assert_eq!(trie.get("ACTSS"), None);
+    assert_eq!(trie.get(""), None);
+    assert!(trie.contains("ACTS"));
+    assert_eq!(trie.count(), 3);
+
+    assert_eq!(trie.add("ACT", "CAT".to_string()), Some("ACT".to_string()));
+    assert_eq!(trie.get("ACT"), Some(&"CAT".to_string()));
+
+    trie.get_mut("AT").unwrap().push('S');
+    assert_eq!(trie.get("AT"), Some(&"ATS".to_string()));
+    assert_eq!(trie.get_mut("A"), None);
+}
+
+#[test]
+fn test_remove() {
+    let mut trie = trie(&["ACT", "ACTS", "AT"]);
+
+    assert_eq!(trie.remove("AC"), None);
+    assert_eq!(trie.remove("ACTS"), Some("ACTS".to_string()));
+    assert_eq!(trie.remove("ACTS"), None);
+    assert_eq!(trie.count(), 2);
+    assert!(trie.contains("ACT"));
+
+    // Branches with nothing left in them are pruned
+    assert_eq!(trie.remove("ACT"), Some("ACT".to_string()));
+    assert!(!trie.entries[&'A'].entries.contains_key(&'C'));
+
+    assert_eq!(trie.remove("AT"), Some("AT".to_string()));
+    assert!(trie.is_empty());
+}
+
+#[test]
+fn test_iter() {
+    let trie = trie(&["BE", "ACTS", "ACT", "AT", "B"]);
+    assert_eq!(
+        trie.iter().map(|(word, _)| word).collect::<Vec<_>>(),
+        vec!["ACT", "ACTS", "AT", "B", "BE"]
+    );
+    assert_eq!(
+        trie.iter_prefix("AC")
+            .map(|(word, _)| word)
+            .collect::<Vec<_>>(),
+        vec!["ACT", "ACTS"]
+    );
+    assert_eq!(
+        trie.iter_prefix("B")
+            .map(|(word, _)| word)
+            .collect::<Vec<_>>(),
+        vec!["B", "BE"]
+    );
+    assert_eq!(trie.iter_prefix("C").count(), 0);
+    assert_eq!(trie.iter_prefix("").count(), 5);
+    assert!(WordTrie::<()>::new().iter().next().is_none());
+}
+
+#[test]
+fn test_find_substrings() {
+    let trie = trie(&["ACT", "ACST", "AT", "EST", "AAT"]);
+
+    let mut found = trie.find_substrings("ACST");
+    found.sort_unstable();
+    assert_eq!(found, vec!["ACST", "ACT", "AT"]);
+
+    // Words are found once for each way of skipping the unused letters
+    let mut found = trie.find_substrings("AAT");
+    found.sort_unstable();
+    assert_eq!(found, vec!["AAT", "AT", "AT"]);
+
+    assert!(trie.find_substrings("").is_empty());
+    assert!(trie.find_substrings("XYZ").is_empty());
+}
+
+#[test]
+fn test_find_with_wildcards() {
+    let trie = trie(&["ACT", "ACST", "AT", "EST"]);
+
+    let mut found = trie.find_with_wildcards("AT", &[Wildcard::Any]);
+    found.sort_unstable();
+    assert_eq!(
+        found,
+        vec![
+            (&"ACT".to_string(), vec![Some('C')]),
+            (&"AT".to_string(), vec![None])
+        ]
+    );
+
+    let vowels = Wildcard::Class(vec!['A', 'E', 'I', 'O', 'U']);
+    let mut found = trie.find_with_wildcards("ST", std::slice::from_ref(&vowels));
+    found.sort_unstable();
+    assert_eq!(
+        found,
+        vec![
+            (&"AT".to_string(), vec![Some('A')]),
+            (&"EST".to_string(), vec![Some('E')])
+        ]
+    );
+
+    // Identical wildcards are interchangeable, so each word is only reported once
+    let found = trie.find_with_wildcards("T", &[Wildcard::Any, Wildcard::Any]);
+    assert_eq!(found.len(), 3);
+
+    let found = trie.find_with_wildcards("", &[vowels, Wildcard::Any]);
+    assert_eq!(found, vec![(&"AT".to_string(), vec![Some('A'), Some('T')])]);
+}
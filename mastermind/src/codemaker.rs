@@ -0,0 +1,98 @@
+use crate::rows::*;
+use crate::strategy::partition_counts;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Secret {
+    Fixed(FatRow),
+    // Never committed to: each guess gets whichever score leaves the most secrets possible
+    Evil,
+}
+
+// A game where the program scores the player's guesses
+#[derive(Clone, Debug)]
+pub struct Game {
+    def: RowDef,
+    secret: Secret,
+    history: Vec<(FatRow, Score)>,
+    // The secrets still consistent with every score given so far
+    candidates: RowSet,
+}
+
+impl Game {
+    pub fn new(all_rows: &RowSet, secret: FatRow) -> Self {
+        Self {
+            def: all_rows.def(),
+            secret: Secret::Fixed(secret),
+            history: vec![],
+            candidates: all_rows.clone(),
+        }
+    }
+
+    pub fn evil(all_rows: &RowSet) -> Self {
+        Self {
+            def: all_rows.def(),
+            secret: Secret::Evil,
+            history: vec![],
+            candidates: all_rows.clone(),
+        }
+    }
+
+    pub fn random(all_rows: &RowSet, rng: &mut impl Rng) -> Option<Self> {
+        let secret = all_rows.iter().choose(rng)?;
+        Some(Self::new(all_rows, secret))
+    }
+
+    pub fn guess(&mut self, guess: FatRow) -> Score {
+        let score = match self.secret {
+            Secret::Fixed(secret) => guess.score(&secret),
+            Secret::Evil => self.worst_score(&guess),
+        };
+        self.candidates = self.candidates.compatible_with(&guess, score);
+        self.history.push((guess, score));
+        score
+    }
+
+    // The score leaving the most candidates, the lowest on a tie, only giving all black when
+    // nothing else is left
+    fn worst_score(&self, guess: &FatRow) -> Score {
+        let parts = partition_counts(guess, &self.candidates);
+        let winning_score = self.def.winning_score();
+        self.def
+            .possible_scores()
+            .into_iter()
+            .rev()
+            .max_by_key(|score| (parts[score.index(self.def)], *score != winning_score))
+            .unwrap_or(winning_score)
+    }
+
+    pub fn def(&self) -> RowDef {
+        self.def
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.history
+            .last()
+            .is_some_and(|(_, score)| *score == self.def.winning_score())
+    }
+
+    pub fn history(&self) -> &[(FatRow, Score)] {
+        &self.history
+    }
+
+    pub fn candidates(&self) -> &RowSet {
+        &self.candidates
+    }
+
+    // An evil codemaker's secret is any of the candidates left
+    pub fn secret(&self) -> Option<FatRow> {
+        match self.secret {
+            Secret::Fixed(secret) => Some(secret),
+            Secret::Evil => self.candidates.iter().next(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_game() -> Result<()> {
+    let def = RowDef::new(4, 6);
+    let mut all_rows = RowSet::new(def)?;
+    all_rows.insert_all();
+
+    let mut game = Game::new(&all_rows, def.row(&[1, 1, 2, 3]));
+    assert_eq!(game.candidates().count(), 1296);
+
+    assert_eq!(game.guess(def.row(&[3, 1, 1, 2])), Score::new(1, 3));
+    assert!(!game.is_won());
+    assert!(game.candidates().contains(&game.secret().unwrap()));
+    assert!(game.candidates().count() < 1296);
+
+    assert_eq!(game.guess(def.row(&[1, 1, 2, 3])), Score::new(4, 0));
+    assert!(game.is_won());
+    assert_eq!(game.history().len(), 2);
+    assert_eq!(game.candidates().count(), 1);
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let game = Game::random(&all_rows, &mut rng).unwrap();
+    assert!(all_rows.contains(&game.secret().unwrap()));
+
+    Ok(())
+}
+
+#[test]
+fn test_evil() -> Result<()> {
+    let def = RowDef::new(4, 6);
+    let mut all_rows = RowSet::new(def)?;
+    all_rows.insert_all();
+
+    // The biggest parts for Knuth's first guess are the 256 secrets scoring nothing, or 1 white
+    let mut game = Game::evil(&all_rows);
+    assert_eq!(game.guess(def.row(&[0, 0, 1, 1])), Score::new(0, 0));
+    assert_eq!(game.candidates().count(), 256);
+
+    // Guessing the last candidate left has to win
+    while game.candidates().count() > 1 {
+        let guess = game.candidates().iter().next().unwrap();
+        game.guess(guess);
+    }
+    let secret = game.secret().unwrap();
+    assert_eq!(game.guess(secret), Score::new(4, 0));
+    assert!(game.is_won());
+
+    Ok(())
+}
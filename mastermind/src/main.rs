@@ -1,13 +1,94 @@
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
-use rayon::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::env;
 use std::io::{self, Write};
-use std::sync::{Mutex, RwLock};
 
+mod codemaker;
 mod rows;
+mod session;
+mod simulate;
+mod strategy;
+mod tree;
+
+#[cfg(test)]
+mod testutil;
+
+use codemaker::*;
 use rows::*;
+use session::*;
+use simulate::*;
+use strategy::*;
+use tree::*;
 
-fn main() -> Result<()> {
+#[derive(Debug, Default)]
+struct Options {
+    strategy: Strategy,
+    objective: Objective,
+    // How many guesses to try at each step when building a strategy tree. "all" finds the best
+    // tree, but is only practical for small games
+    width: usize,
+    build_tree: Option<String>,
+    tree: Option<String>,
+    // Play every secret with the strategy and report how it did
+    simulate: bool,
+    // The program picks a secret and scores the user's guesses
+    codemaker: bool,
+    // The program scores the user's guesses without ever picking a secret
+    evil: bool,
+    seed: Option<u64>,
+    variant: Variant,
+    // The number of digits when playing Bulls and Cows
+    bulls_and_cows: Option<u8>,
+    naming: Naming,
+    resume: Option<String>,
+}
+
+fn parse_args() -> Result<Options> {
+    let mut options = Options {
+        width: 3,
+        ..Options::default()
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("{} needs a value", arg));
+        match arg.as_str() {
+            "-s" | "--strategy" => options.strategy = Strategy::parse(&value()?)?,
+            "--objective" => options.objective = Objective::parse(&value()?)?,
+            "--width" => {
+                options.width = match value()?.as_str() {
+                    "all" => usize::MAX,
+                    width => width.parse()?,
+                }
+            }
+            "--build-tree" => options.build_tree = Some(value()?),
+            "--tree" => options.tree = Some(value()?),
+            "--simulate" => options.simulate = true,
+            "--codemaker" => options.codemaker = true,
+            "--evil" => options.evil = true,
+            "--seed" => options.seed = Some(value()?.parse()?),
+            "--bulls-and-cows" => {
+                let digits = value()?.parse()?;
+                if !(1..=10).contains(&digits) {
+                    return Err(anyhow!("Bulls and Cows needs between 1 and 10 digits"));
+                }
+                options.bulls_and_cows = Some(digits);
+            }
+            "--colours" => options.naming = Naming::parse(&value()?)?,
+            "--resume" => options.resume = Some(value()?),
+            "--variant" => {
+                options.variant = Variant::parse(&value()?.split(',').collect::<Vec<_>>())?
+            }
+            _ => return Err(anyhow!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn read_def(variant: Variant) -> Result<RowDef> {
     print!("Enter Holes, Colours: ");
     io::stdout().flush()?;
 
@@ -33,137 +114,295 @@ fn main() -> Result<()> {
         return Err(anyhow!("Colours must be at least 2"));
     }
 
-    let def = RowDef::new(num_holes, num_colours);
+    Ok(RowDef::with_variant(num_holes, num_colours, variant))
+}
+
+fn print_stats(stats: &Stats) {
+    println!(
+        "{} secrets, {:.4} guesses on average, {} at most",
+        stats.secrets, stats.average, stats.max_depth
+    );
+    for (guesses, count) in stats.distribution.iter().enumerate().skip(1) {
+        println!("  {:2}: {}", guesses, count);
+    }
+}
+
+fn main() -> Result<()> {
+    let options = parse_args()?;
+    let strategy = options.strategy;
+
+    let tree = options
+        .tree
+        .as_deref()
+        .map(|path| Tree::load(path, options.naming))
+        .transpose()?;
+    let session = options
+        .resume
+        .as_deref()
+        .map(|path| Session::load(path, options.naming))
+        .transpose()?;
+    let def = match (&tree, &session) {
+        (Some(tree), Some(session)) if tree.guess.def() != session.def() => {
+            return Err(anyhow!(
+                "The session and strategy tree are for different games"
+            ));
+        }
+        (Some(tree), _) => tree.guess.def(),
+        (None, Some(session)) => session.def(),
+        (None, None) => match options.bulls_and_cows {
+            Some(digits) => RowDef::bulls_and_cows(digits).with_naming(options.naming),
+            None => read_def(options.variant)?.with_naming(options.naming),
+        },
+    };
+
     let empty_set = RowSet::new(def)?;
 
     let mut all_guesses = empty_set.clone();
     all_guesses.insert_all();
     let all_guesses = all_guesses; // remove 'mut'
 
-    let possible_scores = (0..num_holes)
-        .flat_map(|num_black| {
-            (0..(num_holes - num_black)).map(move |num_white| Score::new(num_black, num_white))
-        })
-        .collect_vec();
+    let mut secrets = empty_set.clone();
+    secrets.insert_secrets();
+    let secrets = secrets;
 
-    let mut candidates = all_guesses.clone();
+    if let Some(path) = &options.build_tree {
+        let search = Search {
+            all_guesses: &all_guesses,
+            strategy,
+            objective: options.objective,
+            width: options.width,
+        };
+        let tree = search
+            .build(&secrets)
+            .ok_or_else(|| anyhow!("No strategy tree found"))?;
+        print_stats(&tree.stats(&secrets)?);
+        return tree.save(path);
+    }
 
-    while candidates.count() > 1 {
-        println!("{} candidates left", candidates.count());
+    if options.simulate {
+        print_stats(&simulate(strategy, &all_guesses, &secrets, true)?);
+        return Ok(());
+    }
 
-        let mut scores = Vec::new();
-        scores.resize_with(def.capacity()?, || {
-            RwLock::new(vec![0usize; def.score_count()])
-        });
-
-        let total = candidates.count();
-        let step = total / 1000;
-        let progress = Mutex::new(0);
-        candidates.par_iter().for_each(|answer| {
-            for score in &possible_scores {
-                for guess in &def.compatible_with(&answer, *score) {
-                    scores[guess.index()].write().unwrap()[score.index(def)] += 1;
-                }
-            }
-            let mut progress = progress.lock().unwrap();
-            *progress += 1;
-            if (step == 0) || (*progress % step) == 0 {
-                print!("\r{:.1}%", (*progress as f32) * 100.0 / (total as f32));
-                io::stdout().flush().unwrap();
-            }
-        });
-
-        let guess_scores: Vec<_> = all_guesses
-            .par_iter()
-            .filter_map(|guess| {
-                let (sum, count) = scores[guess.index()]
-                    .read()
-                    .unwrap()
-                    .iter()
-                    .filter(|&&v| v > 0)
-                    .fold((0, 0), |(sum, count), v| (sum + v, count + 1));
-                if count > 0 {
-                    Some((guess, sum * 100 / count))
-                } else {
-                    None
-                }
-            })
-            .collect();
+    if options.evil {
+        return play_codemaker(Game::evil(&secrets));
+    }
+
+    if options.codemaker {
+        let mut rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let game = Game::random(&secrets, &mut rng).ok_or_else(|| anyhow!("No secrets"))?;
+        return play_codemaker(game);
+    }
 
-        if let Some((_, score)) = guess_scores
-            .iter()
-            .min_by_key(|(guess, v)| (*v, !candidates.contains(guess), guess.index()))
+    if let Some(tree) = &tree {
+        print_stats(&tree.stats(&secrets)?);
+    }
+
+    let mut session = session.unwrap_or_else(|| Session::new(def));
+    let candidates = loop {
+        let candidates = session.candidates(&secrets);
+        if candidates.count() <= 1 {
+            break candidates;
+        }
+        println!("{} candidates left", candidates.count());
+
+        if let Some(node) = tree
+            .as_ref()
+            .and_then(|tree| tree.follow(session.history()))
         {
-            let score = score.clone();
-            let (guess_scores_in, guess_scores_out): (Vec<_>, Vec<_>) = guess_scores
-                .into_iter()
-                .filter(|(_, v)| *v == score)
-                .partition(|(guess, _)| candidates.contains(guess));
-
-            let (mut guess_scores, in_candidates) = if guess_scores_in.len() > 0 {
-                (guess_scores_in, true)
-            } else {
-                (guess_scores_out, false)
-            };
-
-            guess_scores.sort_unstable();
-            println!(
-                "\rRecommended guesses ({} with score = {}{}):",
-                guess_scores.len(),
-                (score as f32) / 100.0,
-                if in_candidates {
-                    ""
-                } else {
-                    ", not in candidates"
-                }
-            );
-            for (guess, _) in guess_scores.into_iter().take(10) {
-                println!("  {guess}");
-            }
+            println!("Strategy tree guess:\n  {}", node.guess);
+        } else if let Some(recommendation) =
+            recommend_from_table(strategy, &all_guesses, &candidates)?
+        {
+            print_recommendation(strategy, recommendation);
         } else {
             println!();
             return Err(anyhow!("No best guess found"));
         }
 
-        print!("Enter guess: ");
-        io::stdout().flush()?;
-
-        let mut numbers = String::new();
-        io::stdin().read_line(&mut numbers)?;
-        let numbers: Vec<u8> = numbers
-            .trim()
-            .split(&[' ', ','])
-            .map(str::parse)
-            .collect::<Result<_, _>>()?;
-        if numbers.len() != (num_holes as usize) {
-            return Err(anyhow!("Guess has incorrect number of holes"));
+        // After an undo, start the turn again
+        let guess = match read_guess(&mut session)? {
+            Entry::Value(guess) => guess,
+            Entry::Undo => continue,
+            Entry::Quit => return Ok(()),
+        };
+        match read_score(&candidates, &guess)? {
+            Entry::Value(score) => session.push(guess, score),
+            Entry::Undo => continue,
+            Entry::Quit => return Ok(()),
         }
+    };
+
+    match candidates.iter().next() {
+        Some(solution) => println!("Solution: {}", solution),
+        None => println!("No valid solutions left!"),
+    }
 
-        let guess = def.row(&numbers);
+    Ok(())
+}
+
+// A line of input, or none at the end of input
+fn prompt(text: &str) -> Result<Option<String>> {
+    print!("{}", text);
+    io::stdout().flush()?;
 
-        print!("Enter score (black, white): ");
-        io::stdout().flush()?;
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line)? == 0 {
+        println!();
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_owned()))
+}
 
-        let mut numbers = String::new();
-        io::stdin().read_line(&mut numbers)?;
-        let numbers: Vec<u8> = numbers
-            .trim()
-            .split(&[' ', ','])
-            .map(str::parse)
-            .collect::<Result<_, _>>()?;
-        if numbers.len() != 2 {
-            return Err(anyhow!("Score must have 2 parts"));
+const SESSION_HELP: &str = "\
+Enter a guess, or a command:
+  history    the guesses so far and their scores
+  undo       take back the last guess and score
+  save FILE  save the session, to carry on later with --resume FILE
+  quit       stop";
+
+enum Entry<T> {
+    Value(T),
+    Undo,
+    Quit,
+}
+
+// Handles commands until a guess is entered
+fn read_guess(session: &mut Session) -> Result<Entry<FatRow>> {
+    let def = session.def();
+    loop {
+        let Some(line) = prompt("Enter guess: ")? else {
+            return Ok(Entry::Quit);
+        };
+
+        match line.split_once(' ').unwrap_or((&line, "")) {
+            ("", _) => {}
+            ("help", _) => println!("{}", SESSION_HELP),
+            ("history", _) => print_history(def, session.history()),
+            ("undo", _) => match session.undo() {
+                Some((guess, score)) => {
+                    println!("Undid {}  {}", guess, def.describe_score(score));
+                    return Ok(Entry::Undo);
+                }
+                None => println!("Nothing to undo"),
+            },
+            ("save", path) => match path.trim() {
+                "" => println!("Usage: save FILE"),
+                path => match session.save(path) {
+                    Ok(()) => println!("Saved to {}", path),
+                    Err(e) => println!("Error: {}", e),
+                },
+            },
+            ("quit", _) => return Ok(Entry::Quit),
+            _ => match def.parse_row(&line) {
+                Ok(guess) if !def.is_guess(&guess) => {
+                    println!("Error: {} isn't allowed as a guess", guess)
+                }
+                Ok(guess) => return Ok(Entry::Value(guess)),
+                Err(e) => println!("Error: {}", e),
+            },
         }
+    }
+}
 
-        let score = Score::new(numbers[0], numbers[1]);
+// Reads scores until one leaves a candidate, or undo to go back to the guess
+fn read_score(candidates: &RowSet, guess: &FatRow) -> Result<Entry<Score>> {
+    let def = candidates.def();
+    loop {
+        let text = format!("Enter score ({}), or undo: ", def.score_format());
+        let Some(line) = prompt(&text)? else {
+            return Ok(Entry::Quit);
+        };
 
-        candidates = candidates.compatible_with(&guess, score);
+        match line.as_str() {
+            "undo" => return Ok(Entry::Undo),
+            "quit" => return Ok(Entry::Quit),
+            line => match def.parse_score(line) {
+                Ok(score) if candidates.compatible_with(guess, score).count() == 0 => {
+                    println!("Error: no secret fits every score, undo if one was wrong")
+                }
+                Ok(score) => return Ok(Entry::Value(score)),
+                Err(e) => println!("Error: {}", e),
+            },
+        }
     }
+}
 
-    match candidates.iter().next() {
-        Some(solution) => println!("Solution: {}", solution),
-        None => println!("No valid solutions left!"),
+fn print_history(def: RowDef, history: &[(FatRow, Score)]) {
+    for (i, (guess, score)) in history.iter().enumerate() {
+        println!("{:3}  {}  {}", i + 1, guess, def.describe_score(*score));
+    }
+}
+
+fn recommend_from_table(
+    strategy: Strategy,
+    all_guesses: &RowSet,
+    candidates: &RowSet,
+) -> Result<Option<Recommendation>> {
+    let table = score_table(all_guesses, candidates, true)?;
+    Ok(recommend(strategy, all_guesses, candidates, &table))
+}
+
+fn print_recommendation(strategy: Strategy, recommendation: Recommendation) {
+    println!(
+        "\rRecommended guesses ({} with {} = {:.3}{}):",
+        recommendation.guesses.len(),
+        strategy.name(),
+        strategy.value(recommendation.rank),
+        if recommendation.in_candidates {
+            ""
+        } else {
+            ", not in candidates"
+        }
+    );
+    for guess in recommendation.guesses.into_iter().take(10) {
+        println!("  {guess}");
+    }
+}
+
+const CODEMAKER_HELP: &str = "\
+Enter a guess, or a command:
+  hint     how many secrets are still possible
+  history  the guesses so far and their scores
+  quit     give up and show the secret";
+
+fn play_codemaker(mut game: Game) -> Result<()> {
+    let def = game.def();
+    println!("I've picked a secret, enter help for commands");
+
+    while !game.is_won() {
+        let Some(line) = prompt("Enter guess: ")? else {
+            break;
+        };
+
+        match line.as_str() {
+            "" => {}
+            "help" => println!("{}", CODEMAKER_HELP),
+            "hint" => println!("{} possible secrets left", game.candidates().count()),
+            "history" => print_history(def, game.history()),
+            "quit" => break,
+            line => match def.parse_row(line) {
+                Ok(guess) if !def.is_guess(&guess) => {
+                    println!("Error: {} isn't allowed as a guess", guess)
+                }
+                Ok(guess) => {
+                    let score = game.guess(guess);
+                    println!("{}", def.describe_score(score));
+                }
+                Err(e) => println!("Error: {}", e),
+            },
+        }
     }
 
+    if game.is_won() {
+        println!("Solved in {} guesses", game.history().len());
+    } else {
+        if let Some(secret) = game.secret() {
+            println!("The secret was {}", secret);
+        }
+    }
     Ok(())
 }
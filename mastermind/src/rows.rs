@@ -1,7 +1,8 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use bittyset::BitSet;
 use itertools::{repeat_n, Either, Itertools};
 use rayon::prelude::*;
+use std::cmp::Ordering;
 use std::fmt;
 use std::rc::Rc;
 
@@ -38,6 +39,9 @@ impl Iterator for RowIter {
 pub struct Score {
     num_black: u8,
     num_white: u8,
+    // Positional feedback only: a base 3 digit per hole, first hole first, of 2 for the right
+    // colour, 1 for the right colour in the wrong hole and 0 for neither
+    pattern: u32,
 }
 
 impl Score {
@@ -45,20 +49,69 @@ impl Score {
         Self {
             num_black,
             num_white,
+            pattern: 0,
         }
     }
 
+    fn positional(marks: impl IntoIterator<Item = u8>) -> Self {
+        marks.into_iter().fold(Self::new(0, 0), |score, mark| Self {
+            num_black: score.num_black + (mark == 2) as u8,
+            num_white: score.num_white + (mark == 1) as u8,
+            pattern: score.pattern * 3 + mark as u32,
+        })
+    }
+
+    fn marks(&self, num_holes: u8) -> Vec<u8> {
+        let mut marks: Vec<u8> = (0..num_holes)
+            .scan(self.pattern, |pattern, _| {
+                let mark = *pattern % 3;
+                *pattern /= 3;
+                Some(mark as u8)
+            })
+            .collect();
+        marks.reverse();
+        marks
+    }
+
+    pub fn num_black(&self) -> u8 {
+        self.num_black
+    }
+
+    pub fn num_white(&self) -> u8 {
+        self.num_white
+    }
+
     pub fn index(&self, def: RowDef) -> usize {
-        if self.num_black == def.num_holes {
-            def.score_count() - 1
-        } else {
-            let num_black = self.num_black as usize;
-            let num_white = self.num_white as usize;
-            num_black * (2 * def.num_holes as usize + 3 - num_black) / 2 + num_white
+        match def.variant.feedback {
+            Feedback::BlackWhite | Feedback::TotalOnly if self.num_black == def.num_holes => {
+                def.score_count() - 1
+            }
+            Feedback::BlackWhite => {
+                let num_black = self.num_black as usize;
+                let num_white = self.num_white as usize;
+                num_black * (2 * def.num_holes as usize + 3 - num_black) / 2 + num_white
+            }
+            Feedback::BlackOnly => self.num_black as usize,
+            Feedback::TotalOnly => self.num_white as usize,
+            Feedback::Positional => self.pattern as usize,
         }
     }
 }
 
+// What the codemaker tells the codebreaker about a guess
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Feedback {
+    // A black for each right colour in the right hole, and a white for each other right colour
+    #[default]
+    BlackWhite,
+    // Only the blacks, as in Black-box or Mastermind44
+    BlackOnly,
+    // Only the blacks and whites added together, held as whites, and whether the guess won
+    TotalOnly,
+    // Which holes are right, and which have a right colour in the wrong hole, as in Wordle
+    Positional,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum ScoreType {
     Black,
@@ -157,16 +210,153 @@ impl Iterator for Possibilities {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+// Rule variants, which limit which rows may be secrets or guesses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Variant {
+    // Holes may be left empty, which scores as if blank were one more colour
+    pub blanks: bool,
+    // No colour appears twice, though blanks may
+    pub distinct_secrets: bool,
+    pub distinct_guesses: bool,
+    // Rows are written as strings of digits, and scores as bulls and cows
+    pub digits: bool,
+    pub feedback: Feedback,
+}
+
+impl Variant {
+    pub fn parse(names: &[&str]) -> Result<Self> {
+        let mut variant = Self::default();
+        for name in names {
+            match *name {
+                "blanks" => variant.blanks = true,
+                "distinct" => {
+                    variant.distinct_secrets = true;
+                    variant.distinct_guesses = true;
+                }
+                "distinct-secrets" => variant.distinct_secrets = true,
+                "distinct-guesses" => variant.distinct_guesses = true,
+                "digits" => variant.digits = true,
+                "black-only" => variant.feedback = Feedback::BlackOnly,
+                "total-only" => variant.feedback = Feedback::TotalOnly,
+                "positional" => variant.feedback = Feedback::Positional,
+                _ => return Err(anyhow!("Unknown variant {}", name)),
+            }
+        }
+        Ok(variant)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names = vec![];
+        if self.blanks {
+            names.push("blanks");
+        }
+        match (self.distinct_secrets, self.distinct_guesses) {
+            (true, true) => names.push("distinct"),
+            (true, false) => names.push("distinct-secrets"),
+            (false, true) => names.push("distinct-guesses"),
+            (false, false) => {}
+        }
+        if self.digits {
+            names.push("digits");
+        }
+        match self.feedback {
+            Feedback::BlackWhite => {}
+            Feedback::BlackOnly => names.push("black-only"),
+            Feedback::TotalOnly => names.push("total-only"),
+            Feedback::Positional => names.push("positional"),
+        }
+        names
+    }
+}
+
+// How colours are written. Colours past the named ones are always written as numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Naming {
+    #[default]
+    Numbers,
+    // A letter each, with no spaces between
+    Letters,
+    Names,
+    // Coloured pegs, for terminals that understand ANSI escapes
+    Pegs,
+}
+
+impl Naming {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "numbers" => Ok(Naming::Numbers),
+            "letters" => Ok(Naming::Letters),
+            "names" => Ok(Naming::Names),
+            "pegs" => Ok(Naming::Pegs),
+            _ => Err(anyhow!("Unknown colour naming {}", name)),
+        }
+    }
+}
+
+// The named colours, in colour order, with their letters and ANSI escape colours
+const COLOUR_NAMES: [(char, &str, &str); 10] = [
+    ('R', "red", "31"),
+    ('G', "green", "32"),
+    ('B', "blue", "34"),
+    ('Y', "yellow", "33"),
+    ('W', "white", "97"),
+    ('K', "black", "90"),
+    ('O', "orange", "38;5;208"),
+    ('P', "purple", "38;5;93"),
+    ('C', "cyan", "36"),
+    ('M', "magenta", "95"),
+];
+
+#[derive(Clone, Copy, Debug)]
 pub struct RowDef {
     num_holes: u8,
+    // Including blank, as the last, if allowed
     num_colours: u8,
     base: u8,
     shift: u8,
+    variant: Variant,
+    // Only how rows are shown, so not part of the game compared below
+    naming: Naming,
+}
+
+impl PartialEq for RowDef {
+    fn eq(&self, other: &Self) -> bool {
+        self.game() == other.game()
+    }
+}
+
+impl Eq for RowDef {}
+
+impl PartialOrd for RowDef {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RowDef {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.game().cmp(&other.game())
+    }
 }
 
 impl RowDef {
     pub fn new(num_holes: u8, num_colours: u8) -> Self {
+        Self::with_variant(num_holes, num_colours, Variant::default())
+    }
+
+    // Mastermind with distinct digits for colours
+    pub fn bulls_and_cows(num_holes: u8) -> Self {
+        let variant = Variant {
+            distinct_secrets: true,
+            distinct_guesses: true,
+            digits: true,
+            ..Variant::default()
+        };
+        Self::with_variant(num_holes, 10, variant)
+    }
+
+    pub fn with_variant(num_holes: u8, num_colours: u8, variant: Variant) -> Self {
+        let num_colours = num_colours + variant.blanks as u8;
         let base = num_colours.next_power_of_two();
 
         Self {
@@ -174,6 +364,118 @@ impl RowDef {
             num_colours,
             base,
             shift: base.ilog2() as u8,
+            variant,
+            naming: Naming::default(),
+        }
+    }
+
+    pub fn with_naming(self, naming: Naming) -> Self {
+        Self { naming, ..self }
+    }
+
+    // "HOLES COLOURS [VARIANT...]", for the first line of saved files
+    pub fn header(&self) -> String {
+        let mut header = vec![self.num_holes.to_string(), self.num_colours().to_string()];
+        header.extend(self.variant.names().into_iter().map(str::to_owned));
+        header.join(" ")
+    }
+
+    pub fn parse_header(header: &str, naming: Naming) -> Result<Self> {
+        let bad_header = || anyhow!("Bad header: {}", header);
+        let fields: Vec<_> = header.split_whitespace().collect();
+        match fields[..] {
+            [num_holes, num_colours, ref variant @ ..] => Ok(Self::with_variant(
+                num_holes.parse().map_err(|_| bad_header())?,
+                num_colours.parse().map_err(|_| bad_header())?,
+                Variant::parse(variant)?,
+            )
+            .with_naming(naming)),
+            _ => Err(bad_header()),
+        }
+    }
+
+    pub fn naming(&self) -> Naming {
+        self.naming
+    }
+
+    // What makes two definitions the same game, whatever their naming
+    fn game(&self) -> (u8, u8, Variant) {
+        (self.num_holes, self.num_colours, self.variant)
+    }
+
+    // Whether rows are written without spaces between colours
+    fn is_compact(&self) -> bool {
+        self.variant.digits
+            || (self.naming == Naming::Letters && self.num_colours() as usize <= COLOUR_NAMES.len())
+    }
+
+    fn write_colour(&self, f: &mut fmt::Formatter<'_>, colour: u8) -> fmt::Result {
+        if Some(colour) == self.blank() {
+            return write!(f, "-");
+        }
+        match (self.naming, COLOUR_NAMES.get(colour as usize)) {
+            (Naming::Letters, Some((letter, _, _))) => write!(f, "{}", letter),
+            (Naming::Names, Some((_, name, _))) => write!(f, "{}", name),
+            (Naming::Pegs, Some((_, _, escape))) => write!(f, "\x1b[{}m\u{25cf}\x1b[0m", escape),
+            _ => write!(f, "{}", colour),
+        }
+    }
+
+    // A number, letter or name, whatever the naming, or '-' for blank
+    fn parse_colour(&self, peg: &str) -> Option<u8> {
+        let colour = match (peg, self.blank()) {
+            ("-", Some(blank)) => return Some(blank),
+            _ => peg.parse().ok().or_else(|| {
+                COLOUR_NAMES
+                    .iter()
+                    .position(|(letter, name, _)| {
+                        peg.eq_ignore_ascii_case(name)
+                            || peg.eq_ignore_ascii_case(&letter.to_string())
+                    })?
+                    .try_into()
+                    .ok()
+            })?,
+        };
+        (colour < self.num_colours()).then_some(colour)
+    }
+
+    pub fn num_holes(&self) -> u8 {
+        self.num_holes
+    }
+
+    // Not counting blank
+    pub fn num_colours(&self) -> u8 {
+        self.num_colours - self.variant.blanks as u8
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    // The colour used for an empty hole
+    pub fn blank(&self) -> Option<u8> {
+        self.variant.blanks.then_some(self.num_colours - 1)
+    }
+
+    pub fn is_secret(&self, row: &FatRow) -> bool {
+        !self.variant.distinct_secrets || self.is_distinct(row.row)
+    }
+
+    pub fn is_guess(&self, row: &FatRow) -> bool {
+        !self.variant.distinct_guesses || self.is_distinct(row.row)
+    }
+
+    fn is_distinct(&self, row: Row) -> bool {
+        let mut seen = [false; 256];
+        self.row_iter(row)
+            .filter(|&colour| Some(colour) != self.blank())
+            .all(|colour| !std::mem::replace(&mut seen[colour as usize], true))
+    }
+
+    pub fn winning_score(&self) -> Score {
+        match self.variant.feedback {
+            Feedback::Positional => Score::positional(repeat_n(2, self.num_holes.into())),
+            _ => Score::new(self.num_holes, 0),
         }
     }
 
@@ -181,11 +483,23 @@ impl RowDef {
         1usize << (self.shift * self.num_holes)
     }
 
-    pub fn score(&self, a: Row, b: Row) -> Score {
-        let mut score = Score {
-            num_black: 0,
-            num_white: 0,
-        };
+    pub fn score(&self, guess: Row, secret: Row) -> Score {
+        if self.variant.feedback == Feedback::Positional {
+            return self.positional_score(guess, secret);
+        }
+
+        let score = self.black_white_score(guess, secret);
+        match self.variant.feedback {
+            Feedback::BlackOnly => Score::new(score.num_black, 0),
+            Feedback::TotalOnly if score != self.winning_score() => {
+                Score::new(0, score.num_black + score.num_white)
+            }
+            _ => score,
+        }
+    }
+
+    fn black_white_score(&self, a: Row, b: Row) -> Score {
+        let mut score = Score::new(0, 0);
         let mut spares_a: Vec<u8> = vec![0; self.num_colours.into()];
         let mut spares_b: Vec<u8> = vec![0; self.num_colours.into()];
 
@@ -212,9 +526,66 @@ impl RowDef {
         score
     }
 
+    // Marks the right colours first, then the rest from the first hole on while the secret has
+    // unmarked pegs of their colour
+    fn positional_score(&self, guess: Row, secret: Row) -> Score {
+        let mut spares: Vec<u8> = vec![0; self.num_colours.into()];
+        let mut marks: Vec<u8> = vec![0; self.num_holes.into()];
+
+        for (i, (ge, se)) in self.row_iter(guess).zip(self.row_iter(secret)).enumerate() {
+            if ge == se {
+                marks[i] = 2;
+            } else {
+                spares[se as usize] += 1;
+            }
+        }
+        for (i, ge) in self.row_iter(guess).enumerate() {
+            if marks[i] == 0 && spares[ge as usize] > 0 {
+                marks[i] = 1;
+                spares[ge as usize] -= 1;
+            }
+        }
+
+        Score::positional(marks)
+    }
+
+    // Every score a guess can get, leaving out all but one black and one white
+    pub fn possible_scores(&self) -> Vec<Score> {
+        let num_holes = self.num_holes;
+        let scores: Vec<_> = match self.variant.feedback {
+            Feedback::BlackWhite => (0..=num_holes)
+                .flat_map(|num_black| {
+                    (0..=(num_holes - num_black))
+                        .map(move |num_white| Score::new(num_black, num_white))
+                })
+                .collect(),
+            Feedback::BlackOnly => (0..=num_holes)
+                .map(|num_black| Score::new(num_black, 0))
+                .collect(),
+            Feedback::TotalOnly => (0..=num_holes)
+                .map(|total| Score::new(0, total))
+                .chain([self.winning_score()])
+                .collect(),
+            Feedback::Positional => repeat_n(0..3, num_holes.into())
+                .multi_cartesian_product()
+                .map(Score::positional)
+                .collect(),
+        };
+        scores
+            .into_iter()
+            .filter(|score| (score.num_black, score.num_white) != (num_holes - 1, 1))
+            .collect()
+    }
+
+    // One more than the highest `Score::index`
     pub fn score_count(&self) -> usize {
         let num_holes = self.num_holes as usize;
-        (num_holes + 2) * (num_holes + 1) / 2 - 1
+        match self.variant.feedback {
+            Feedback::BlackWhite => (num_holes + 2) * (num_holes + 1) / 2 - 1,
+            Feedback::BlackOnly => num_holes + 1,
+            Feedback::TotalOnly => num_holes + 2,
+            Feedback::Positional => 3usize.pow(num_holes as u32),
+        }
     }
 
     fn row_iter(&self, row: Row) -> RowIter {
@@ -230,11 +601,7 @@ impl RowDef {
             .checked_next_power_of_two()
             .context("Too many colours")?;
 
-        usize::checked_pow(
-            base.try_into().expect("usize should be > u8"),
-            self.num_holes.try_into().expect("usize should be >= u32"),
-        )
-        .context("Too many possibilities")
+        usize::checked_pow(base.into(), self.num_holes.into()).context("Too many possibilities")
     }
 
     #[cfg(test)]
@@ -242,6 +609,129 @@ impl RowDef {
         usize::pow(self.num_colours as usize, self.num_holes as u32)
     }
 
+    // Parses colours separated by spaces or commas, with '-' for blank, checking that they make a
+    // row of this definition. Letters, and digits in the digits variant, may also run together.
+    pub fn parse_row(&self, line: &str) -> Result<FatRow> {
+        let mut pegs: Vec<u8> = vec![];
+        for token in line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|token| !token.is_empty())
+        {
+            let whole = || self.parse_colour(token).map(|colour| vec![colour]);
+            let split = || {
+                token
+                    .char_indices()
+                    .map(|(i, c)| self.parse_colour(&token[i..i + c.len_utf8()]))
+                    .collect::<Option<Vec<_>>>()
+            };
+            let colours = if self.variant.digits {
+                // Every digit is a peg, so 01 is 0 1 rather than the number 1
+                split().or_else(whole)
+            } else if token.contains(|c: char| c.is_ascii_digit()) {
+                // Splitting numbers outside the digits variant would read 12 as 1 2
+                whole()
+            } else {
+                whole().or_else(split)
+            };
+            pegs.extend(colours.ok_or_else(|| anyhow!("There is no colour {}", token))?);
+        }
+        if pegs.len() != (self.num_holes as usize) {
+            return Err(anyhow!("Guess has incorrect number of holes"));
+        }
+        Ok(self.row(&pegs))
+    }
+
+    pub fn describe_score(&self, score: Score) -> String {
+        let plural =
+            |n: u8, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        match self.variant.feedback {
+            Feedback::BlackWhite if self.variant.digits => format!(
+                "{}, {}",
+                plural(score.num_black, "bull", "bulls"),
+                plural(score.num_white, "cow", "cows")
+            ),
+            Feedback::BlackWhite => format!("Black {}, white {}", score.num_black, score.num_white),
+            Feedback::BlackOnly => format!("Black {}", score.num_black),
+            Feedback::TotalOnly if score == self.winning_score() => "Solved".to_owned(),
+            Feedback::TotalOnly => format!("Total {}", score.num_white),
+            Feedback::Positional => self.format_score(score),
+        }
+    }
+
+    // How to enter a score, for prompts
+    pub fn score_format(&self) -> &'static str {
+        match self.variant.feedback {
+            Feedback::BlackWhite if self.variant.digits => "bulls, cows",
+            Feedback::BlackWhite => "black, white",
+            Feedback::BlackOnly => "black",
+            Feedback::TotalOnly => "total, or win",
+            Feedback::Positional => "g, y or . per hole",
+        }
+    }
+
+    // A score with no spaces, which `parse_score` reads back
+    pub fn format_score(&self, score: Score) -> String {
+        match self.variant.feedback {
+            Feedback::BlackWhite => format!("{},{}", score.num_black, score.num_white),
+            Feedback::BlackOnly => score.num_black.to_string(),
+            Feedback::TotalOnly if score == self.winning_score() => "win".to_owned(),
+            Feedback::TotalOnly => score.num_white.to_string(),
+            Feedback::Positional => score
+                .marks(self.num_holes)
+                .into_iter()
+                .map(|mark| ['.', 'y', 'g'][mark as usize])
+                .collect(),
+        }
+    }
+
+    // Parses a score in the form `score_format` gives, checking that a guess could get it
+    pub fn parse_score(&self, line: &str) -> Result<Score> {
+        let line = line.trim();
+        let bad_score = || anyhow!("Bad score {}", line);
+        let numbers = || -> Result<Vec<u8>> {
+            line.split(&[' ', ','])
+                .filter(|n| !n.is_empty())
+                .map(|n| n.parse().map_err(|_| bad_score()))
+                .collect()
+        };
+
+        let score = match self.variant.feedback {
+            Feedback::BlackWhite => match numbers()?[..] {
+                [num_black, num_white] => Score::new(num_black, num_white),
+                _ => return Err(anyhow!("Score must have 2 parts")),
+            },
+            Feedback::BlackOnly => match numbers()?[..] {
+                [num_black] => Score::new(num_black, 0),
+                _ => return Err(bad_score()),
+            },
+            Feedback::TotalOnly if line == "win" => self.winning_score(),
+            Feedback::TotalOnly => match numbers()?[..] {
+                [total] => Score::new(0, total),
+                _ => return Err(bad_score()),
+            },
+            Feedback::Positional => {
+                let marks = line
+                    .chars()
+                    .map(|c| match c {
+                        'g' => Ok(2),
+                        'y' => Ok(1),
+                        '.' => Ok(0),
+                        _ => Err(bad_score()),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if marks.len() != self.num_holes as usize {
+                    return Err(anyhow!("Score has incorrect number of holes"));
+                }
+                Score::positional(marks)
+            }
+        };
+
+        if !self.possible_scores().contains(&score) {
+            return Err(anyhow!("No guess can score {}", line));
+        }
+        Ok(score)
+    }
+
     pub fn row(&self, pegs: &[u8]) -> FatRow {
         assert!(pegs.len() as u8 == self.num_holes);
 
@@ -254,9 +744,20 @@ impl RowDef {
         }
     }
 
+    // Every row that, as the secret, would give the guess the score
     pub fn compatible_with(&self, guess: &FatRow, score: Score) -> RowSet {
         use ScoreType::*;
 
+        if self.variant.feedback != Feedback::BlackWhite {
+            // Only black and white scores can be built up a hole at a time
+            let mut set = RowSet {
+                def: *self,
+                set: BitSet::with_capacity(self.capacity().unwrap()),
+            };
+            set.insert_where(|row| guess.score(row) == score);
+            return set;
+        }
+
         let def = self;
         let num_holes = def.num_holes as usize;
         let num_colours = def.num_colours;
@@ -367,14 +868,26 @@ impl FatRow {
         self.row.0
     }
 
+    pub fn def(&self) -> RowDef {
+        self.def
+    }
+
+    pub fn with_naming(self, naming: Naming) -> Self {
+        Self {
+            def: self.def.with_naming(naming),
+            ..self
+        }
+    }
+
     pub fn score(&self, other: &FatRow) -> Score {
         debug_assert_eq!(self.def, other.def);
         self.def.score(self.row, other.row)
     }
 
-    pub fn compatible_with(&self, other: &FatRow, score: Score) -> bool {
-        debug_assert_eq!(self.def, other.def);
-        self.def.score(self.row, other.row) == score
+    // Whether this could be the secret when the guess got the score
+    pub fn compatible_with(&self, guess: &FatRow, score: Score) -> bool {
+        debug_assert_eq!(self.def, guess.def);
+        self.def.score(guess.row, self.row) == score
     }
 }
 
@@ -383,12 +896,12 @@ impl fmt::Display for FatRow {
         let mut first = true;
 
         for e in self.def.row_iter(self.row) {
-            if !first {
+            if !first && !self.def.is_compact() {
                 write!(f, " ")?;
             } else {
                 first = false;
             }
-            write!(f, "{}", e)?;
+            self.def.write_colour(f, e)?;
         }
 
         Ok(())
@@ -433,13 +946,38 @@ impl RowSet {
         })
     }
 
+    pub fn def(&self) -> RowDef {
+        self.def
+    }
+
+    // Every row the variant allows as a guess
     pub fn insert_all(&mut self) {
+        let def = self.def;
+        self.insert_where(|row| def.is_guess(row));
+    }
+
+    // Every row the variant allows as a secret
+    pub fn insert_secrets(&mut self) {
+        let def = self.def;
+        self.insert_where(|row| def.is_secret(row));
+    }
+
+    fn insert_where(&mut self, allowed: impl Fn(&FatRow) -> bool) {
         let possible_rows = AllPossibleRowsIter::new(self.def);
-        for i in possible_rows {
-            self.set.insert(i.0);
+        for row in possible_rows {
+            if allowed(&FatRow { row, def: self.def }) {
+                self.set.insert(row.0);
+            }
         }
     }
 
+    // An empty set for the same rows
+    pub fn cleared(&self) -> Self {
+        let mut set = self.clone();
+        set.set.clear();
+        set
+    }
+
     pub fn insert(&mut self, row: &FatRow) {
         self.set.insert(row.index());
     }
@@ -44,94 +44,34 @@ fn test_score() {
     let answer = def.row(&[1, 1, 2, 3]);
 
     let guess = def.row(&[1, 1, 2, 3]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 4,
-            num_white: 0
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(4, 0));
 
     let guess = def.row(&[1, 1, 4, 3]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 3,
-            num_white: 0
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(3, 0));
 
     let guess = def.row(&[1, 0, 4, 3]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 2,
-            num_white: 0
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(2, 0));
 
     let guess = def.row(&[7, 0, 4, 3]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 1,
-            num_white: 0
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(1, 0));
 
     let guess = def.row(&[7, 0, 4, 7]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 0,
-            num_white: 0
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(0, 0));
 
     let guess = def.row(&[1, 1, 3, 4]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 2,
-            num_white: 1
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(2, 1));
 
     let guess = def.row(&[1, 1, 4, 2]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 2,
-            num_white: 1
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(2, 1));
 
     let guess = def.row(&[1, 1, 1, 2]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 2,
-            num_white: 1
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(2, 1));
 
     let guess = def.row(&[3, 1, 2, 1]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 2,
-            num_white: 2
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(2, 2));
 
     let guess = def.row(&[3, 1, 1, 2]);
-    assert_eq!(
-        answer.score(&guess),
-        Score {
-            num_black: 1,
-            num_white: 3
-        }
-    );
+    assert_eq!(answer.score(&guess), Score::new(1, 3));
 }
 
 fn compatible_with(def: RowDef, guess: &FatRow, score: Score) -> RowSet {
@@ -161,10 +101,7 @@ fn test_compatible_with_3() -> Result<()> {
 
     let guess = def.row(&[3, 1, 3]);
 
-    let score = Score {
-        num_black: 1,
-        num_white: 1,
-    };
+    let score = Score::new(1, 1);
     let mut set = RowSet::new(def)?;
     set.insert_all();
     assert_eq!(
@@ -172,10 +109,7 @@ fn test_compatible_with_3() -> Result<()> {
         compatible_with(def, &guess, score)
     );
 
-    let score = Score {
-        num_black: 1,
-        num_white: 2,
-    };
+    let score = Score::new(1, 2);
     let mut set = RowSet::new(def)?;
     set.insert_all();
     assert_eq!(
@@ -183,10 +117,7 @@ fn test_compatible_with_3() -> Result<()> {
         compatible_with(def, &guess, score)
     );
 
-    let score = Score {
-        num_black: 0,
-        num_white: 0,
-    };
+    let score = Score::new(0, 0);
     let mut set = RowSet::new(def)?;
     set.insert_all();
     assert_eq!(
@@ -203,10 +134,7 @@ fn test_compatible_with_4() -> Result<()> {
 
     let guess = def.row(&[3, 1, 2, 1]);
 
-    let score = Score {
-        num_black: 1,
-        num_white: 1,
-    };
+    let score = Score::new(1, 1);
     let mut set = RowSet::new(def)?;
     set.insert_all();
     assert_eq!(
@@ -223,3 +151,172 @@ fn test_compatible_with_4() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_variants() -> Result<()> {
+    let count = |def: RowDef| -> Result<(usize, usize)> {
+        let mut guesses = RowSet::new(def)?;
+        guesses.insert_all();
+        let mut secrets = RowSet::new(def)?;
+        secrets.insert_secrets();
+        Ok((guesses.count(), secrets.count()))
+    };
+
+    let variant = Variant::parse(&["distinct"])?;
+    assert_eq!(count(RowDef::with_variant(4, 6, variant))?, (360, 360));
+
+    let variant = Variant::parse(&["distinct-secrets"])?;
+    assert_eq!(count(RowDef::with_variant(4, 6, variant))?, (1296, 360));
+
+    let variant = Variant::parse(&["blanks"])?;
+    let def = RowDef::with_variant(4, 6, variant);
+    assert_eq!(count(def)?, (2401, 2401));
+    assert_eq!(def.num_colours(), 6);
+
+    // Blanks score like any other colour, and may repeat even when colours can't
+    let row = def.parse_row("1 - 2 -")?;
+    assert_eq!(row.to_string(), "1 - 2 -");
+    assert_eq!(row.score(&def.parse_row("- 1 2 -")?), Score::new(2, 2));
+    assert!(def.parse_row("1 6 2 -").is_err());
+    assert!(RowDef::new(4, 6).parse_row("1 - 2 -").is_err());
+
+    let variant = Variant::parse(&["blanks", "distinct"])?;
+    let def = RowDef::with_variant(4, 6, variant);
+    assert!(def.is_secret(&def.parse_row("1 - 2 -")?));
+    assert!(!def.is_secret(&def.parse_row("1 - 1 -")?));
+    assert_eq!(Variant::parse(&variant.names())?, variant);
+
+    Ok(())
+}
+
+#[test]
+fn test_bulls_and_cows() -> Result<()> {
+    let def = RowDef::bulls_and_cows(4);
+    let mut secrets = RowSet::new(def)?;
+    secrets.insert_secrets();
+    assert_eq!(secrets.count(), 5040);
+
+    let secret = def.parse_row("1234")?;
+    assert_eq!(secret, def.parse_row("1 2 3 4")?);
+    assert_eq!(secret.to_string(), "1234");
+    assert!(!def.is_guess(&def.parse_row("1123")?));
+    assert!(def.parse_row("123").is_err());
+
+    // A leading zero is a peg of its own
+    assert_eq!(def.parse_row("0123")?, def.row(&[0, 1, 2, 3]));
+    assert_eq!(def.parse_row("01 23")?, def.row(&[0, 1, 2, 3]));
+    let two = RowDef::bulls_and_cows(2);
+    assert_eq!(two.parse_row("01")?.to_string(), "01");
+    assert_eq!(two.parse_row("05")?, two.row(&[0, 5]));
+
+    let score = secret.score(&def.parse_row("1359")?);
+    assert_eq!(score, Score::new(1, 1));
+    assert_eq!(def.describe_score(score), "1 bull, 1 cow");
+    assert_eq!(def.describe_score(Score::new(0, 2)), "0 bulls, 2 cows");
+
+    Ok(())
+}
+
+#[test]
+fn test_feedback() -> Result<()> {
+    let with_feedback =
+        |name| -> Result<RowDef> { Ok(RowDef::with_variant(4, 6, Variant::parse(&[name])?)) };
+
+    // Every score has its own index below the count, and every candidate gets a possible score
+    for name in ["black-only", "total-only", "positional"] {
+        let def = with_feedback(name)?;
+        let scores = def.possible_scores();
+        let indexes: Vec<_> = scores.iter().map(|score| score.index(def)).collect();
+        assert!(indexes.iter().all_unique());
+        assert!(indexes.iter().all(|&index| index < def.score_count()));
+        assert_eq!(def.winning_score().index(def), def.score_count() - 1);
+
+        let mut all = RowSet::new(def)?;
+        all.insert_all();
+        let guess = def.parse_row("0 0 1 2")?;
+        let total: usize = scores
+            .iter()
+            .map(|score| all.compatible_with(&guess, *score).count())
+            .sum();
+        assert_eq!(total, 1296);
+        for score in scores {
+            assert_eq!(def.parse_score(&def.format_score(score))?, score);
+        }
+    }
+
+    let def = with_feedback("black-only")?;
+    let score = def.parse_row("1 1 2 3")?.score(&def.parse_row("1 2 1 3")?);
+    assert_eq!(def.describe_score(score), "Black 2");
+
+    let def = with_feedback("total-only")?;
+    let guess = def.parse_row("1 1 2 3")?;
+    let score = guess.score(&def.parse_row("1 2 1 3")?);
+    assert_eq!(def.describe_score(score), "Total 4");
+    assert_ne!(score, def.winning_score());
+    assert_eq!(def.describe_score(guess.score(&guess)), "Solved");
+
+    // Like Wordle, a repeated colour is only marked as often as the secret has it
+    let def = with_feedback("positional")?;
+    let guess = def.parse_row("1 1 2 1")?;
+    let score = guess.score(&def.parse_row("3 1 1 4")?);
+    assert_eq!(def.format_score(score), "yg..");
+    assert_eq!((score.num_black(), score.num_white()), (1, 1));
+    assert!(def.parse_score("gggy").is_err());
+    assert!(def.parse_score("gg").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_naming() -> Result<()> {
+    let def = RowDef::new(4, 6).with_naming(Naming::Letters);
+    let row = def.parse_row("RGBY")?;
+    assert_eq!(row, def.row(&[0, 1, 2, 3]));
+    assert_eq!(row.to_string(), "RGBY");
+
+    // Numbers, letters and names all work whatever the naming
+    assert_eq!(def.parse_row("0 g Blue 3")?, row);
+    assert_eq!(RowDef::new(4, 6).parse_row("r,g,b,y")?, row);
+    assert!(def.parse_row("RGBO").is_err());
+    assert!(def.parse_row("RGB").is_err());
+    assert!(RowDef::new(4, 12).parse_row("1 12 3").is_err());
+
+    let def = RowDef::new(4, 6).with_naming(Naming::Names);
+    assert_eq!(def.row(&[0, 1, 5, 3]).to_string(), "red green black yellow");
+
+    // Past the named colours, letters fall back to spaced numbers
+    let def = RowDef::new(3, 12).with_naming(Naming::Letters);
+    assert_eq!(def.row(&[0, 11, 9]).to_string(), "R 11 M");
+    assert_eq!(def.parse_row("R 11 M")?, def.row(&[0, 11, 9]));
+
+    let variant = Variant::parse(&["blanks"])?;
+    let def = RowDef::with_variant(4, 6, variant).with_naming(Naming::Letters);
+    assert_eq!(def.parse_row("R-B-")?.to_string(), "R-B-");
+
+    Ok(())
+}
+
+#[test]
+fn test_naming_is_not_identity() -> Result<()> {
+    let def = RowDef::new(4, 6);
+    let named = def.with_naming(Naming::Pegs);
+    assert_eq!(named, def);
+    assert_eq!(named.cmp(&def), Ordering::Equal);
+    assert_ne!(named, RowDef::new(4, 7).with_naming(Naming::Pegs));
+    assert_ne!(
+        def.row(&[0, 1, 2, 3]).to_string(),
+        named.row(&[0, 1, 2, 3]).to_string()
+    );
+    assert_eq!(def.row(&[0, 1, 2, 3]), named.row(&[0, 1, 2, 3]));
+
+    // Rows of either can be scored against the other's secrets
+    let mut secrets = RowSet::new(named)?;
+    secrets.insert_secrets();
+    let guess = def.parse_row("0 0 1 1")?;
+    assert_eq!(
+        secrets.compatible_with(&guess, def.winning_score()).count(),
+        1
+    );
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crate::rows::*;
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter};
+
+#[cfg(test)]
+mod test;
+
+// The guesses and scores entered while breaking a code, which can be undone, saved and resumed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    def: RowDef,
+    history: Vec<(FatRow, Score)>,
+}
+
+impl Session {
+    pub fn new(def: RowDef) -> Self {
+        Self {
+            def,
+            history: vec![],
+        }
+    }
+
+    pub fn def(&self) -> RowDef {
+        self.def
+    }
+
+    pub fn history(&self) -> &[(FatRow, Score)] {
+        &self.history
+    }
+
+    pub fn push(&mut self, guess: FatRow, score: Score) {
+        self.history.push((guess, score));
+    }
+
+    pub fn undo(&mut self) -> Option<(FatRow, Score)> {
+        self.history.pop()
+    }
+
+    // The secrets consistent with every score so far
+    pub fn candidates(&self, secrets: &RowSet) -> RowSet {
+        self.history
+            .iter()
+            .fold(secrets.clone(), |candidates, (guess, score)| {
+                candidates.compatible_with(guess, *score)
+            })
+    }
+
+    // Saves as "HOLES COLOURS [VARIANT...]" followed by a "GUESS: SCORE" line per guess
+    pub fn save(&self, path: &str) -> Result<()> {
+        let mut f = BufWriter::new(File::create(path)?);
+        writeln!(f, "{}", self.def.header())?;
+        for (guess, score) in &self.history {
+            writeln!(
+                f,
+                "{}: {}",
+                guess.with_naming(Naming::Numbers),
+                self.def.format_score(*score)
+            )?;
+        }
+        f.flush()?;
+        Ok(())
+    }
+
+    pub fn load(path: &str, naming: Naming) -> Result<Self> {
+        let f = BufReader::new(File::open(path)?);
+        let mut lines = f.lines();
+
+        let header = lines.next().context("Empty session file")??;
+        let def = RowDef::parse_header(&header, naming).context("Bad session")?;
+
+        let mut session = Self::new(def);
+        for line in lines {
+            let line = line?;
+            let bad_line = || anyhow!("Bad session line: {}", line);
+
+            let (guess, score) = line.split_once(':').ok_or_else(bad_line)?;
+            let guess = def.parse_row(guess).map_err(|_| bad_line())?;
+            let score = def.parse_score(score).map_err(|_| bad_line())?;
+            session.push(guess, score);
+        }
+        Ok(session)
+    }
+}
@@ -0,0 +1,54 @@
+use super::*;
+use crate::testutil::temp_path;
+
+#[test]
+fn test_undo() -> Result<()> {
+    let def = RowDef::new(4, 6);
+    let mut secrets = RowSet::new(def)?;
+    secrets.insert_secrets();
+
+    let mut session = Session::new(def);
+    let guess = def.parse_row("0 0 1 1")?;
+    session.push(guess, Score::new(1, 0));
+    let candidates = session.candidates(&secrets);
+    assert_eq!(
+        candidates,
+        secrets.compatible_with(&guess, Score::new(1, 0))
+    );
+
+    session.push(def.parse_row("0 2 3 4")?, Score::new(0, 0));
+    assert!(session.candidates(&secrets).count() < candidates.count());
+
+    assert_eq!(
+        session.undo(),
+        Some((def.parse_row("0 2 3 4")?, Score::new(0, 0)))
+    );
+    assert_eq!(session.candidates(&secrets), candidates);
+    session.undo();
+    assert_eq!(session.undo(), None);
+    assert_eq!(session.candidates(&secrets), secrets);
+
+    Ok(())
+}
+
+#[test]
+fn test_save_load() -> Result<()> {
+    let variant = Variant::parse(&["positional"])?;
+    let def = RowDef::with_variant(4, 6, variant).with_naming(Naming::Letters);
+    let mut session = Session::new(def);
+    let guess = def.parse_row("RRGB")?;
+    session.push(guess, guess.score(&def.parse_row("GRBY")?));
+    let guess = def.parse_row("GRBY")?;
+    session.push(guess, def.winning_score());
+
+    let path = &temp_path("session");
+    session.save(path)?;
+    let saved = std::fs::read_to_string(path)?;
+    let loaded = Session::load(path, Naming::Letters);
+    std::fs::remove_file(path)?;
+
+    assert_eq!(saved, "4 6 positional\n0 0 1 2: .gyy\n1 0 2 3: gggg\n");
+    assert_eq!(loaded?, session);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use crate::rows::*;
+use crate::strategy::*;
+use crate::tree::Stats;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+#[cfg(test)]
+mod test;
+
+// Plays a game against every secret, always making the strategy's first recommended guess
+pub fn simulate(
+    strategy: Strategy,
+    all_guesses: &RowSet,
+    secrets: &RowSet,
+    show_progress: bool,
+) -> Result<Stats> {
+    let def = all_guesses.def();
+    let max_guesses = all_guesses.count();
+
+    // The strategy always makes the same guess after the same scores
+    let mut guesses: BTreeMap<Vec<Score>, FatRow> = BTreeMap::new();
+    let mut stats = Stats::default();
+
+    for secret in secrets {
+        let mut candidates = secrets.clone();
+        let mut scores = vec![];
+        loop {
+            let guess = match guesses.get(&scores) {
+                Some(guess) => *guess,
+                None => {
+                    let table = partition_table(all_guesses, &candidates)?;
+                    let guess = recommend(strategy, all_guesses, &candidates, &table)
+                        .and_then(|recommendation| recommendation.guesses.first().copied())
+                        .ok_or_else(|| anyhow!("No best guess found for {}", secret))?;
+                    guesses.insert(scores.clone(), guess);
+                    guess
+                }
+            };
+
+            let score = guess.score(&secret);
+            if score == def.winning_score() {
+                stats.add(scores.len() + 1);
+                break;
+            }
+            if scores.len() == max_guesses {
+                return Err(anyhow!("Gave up trying to find {}", secret));
+            }
+            candidates = candidates.compatible_with(&guess, score);
+            scores.push(score);
+        }
+
+        if show_progress {
+            print!("\r{}/{}", stats.secrets, secrets.count());
+            io::stdout().flush()?;
+        }
+    }
+    if show_progress {
+        println!();
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,27 @@
+use super::*;
+use crate::tree::{Objective, Search};
+
+#[test]
+fn test_simulate() -> Result<()> {
+    let def = RowDef::new(3, 4);
+    let mut all_guesses = RowSet::new(def)?;
+    all_guesses.insert_all();
+
+    let stats = simulate(Strategy::Minimax, &all_guesses, &all_guesses, false)?;
+    assert_eq!(stats.secrets, 64);
+    assert_eq!(stats.distribution.iter().sum::<usize>(), 64);
+    assert_eq!(stats.distribution[1], 1);
+
+    // Playing greedily is the same as following the narrowest strategy tree
+    let tree = Search {
+        all_guesses: &all_guesses,
+        strategy: Strategy::Minimax,
+        objective: Objective::Expected,
+        width: 1,
+    }
+    .build(&all_guesses)
+    .unwrap();
+    assert_eq!(tree.stats(&all_guesses)?, stats);
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use crate::rows::*;
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use std::io::{self, Write};
+use std::sync::{Mutex, RwLock};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    // The mean size of the non-empty parts
+    #[default]
+    Average,
+    // Knuth's: the size of the largest part
+    Minimax,
+    // The information the score gives, in bits
+    Entropy,
+    // The number of non-empty parts
+    MostParts,
+    // The size of the part the answer is expected to be in
+    ExpectedSize,
+}
+
+impl Strategy {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "average" => Ok(Strategy::Average),
+            "minimax" | "knuth" => Ok(Strategy::Minimax),
+            "entropy" => Ok(Strategy::Entropy),
+            "most-parts" => Ok(Strategy::MostParts),
+            "expected-size" => Ok(Strategy::ExpectedSize),
+            _ => Err(anyhow!("Unknown strategy {}", name)),
+        }
+    }
+
+    // How good a guess is from the number of candidates giving each score, where lower is better
+    pub fn rank(&self, parts: &[usize]) -> f64 {
+        // Sorted, so that guesses with the same parts in a different order rank exactly the same
+        let mut parts = parts
+            .iter()
+            .filter(|&&v| v > 0)
+            .map(|&v| v as f64)
+            .collect::<Vec<_>>();
+        parts.sort_unstable_by(f64::total_cmp);
+        let total: f64 = parts.iter().sum();
+
+        match self {
+            Strategy::Average => total / parts.len().max(1) as f64,
+            Strategy::Minimax => parts.last().copied().unwrap_or(0.0),
+            Strategy::Entropy => parts.iter().map(|v| v / total * (v / total).log2()).sum(),
+            Strategy::MostParts => -(parts.len() as f64),
+            Strategy::ExpectedSize => parts.iter().map(|v| v * v).sum::<f64>() / total,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Average => "average",
+            Strategy::Minimax => "worst case",
+            Strategy::Entropy => "entropy",
+            Strategy::MostParts => "parts",
+            Strategy::ExpectedSize => "expected size",
+        }
+    }
+
+    // The value shown for a rank
+    pub fn value(&self, rank: f64) -> f64 {
+        match self {
+            Strategy::Entropy | Strategy::MostParts => -rank,
+            _ => rank,
+        }
+    }
+}
+
+// For every guess, indexed by `FatRow::index`, how many of the candidates give each score,
+// indexed by `Score::index`
+pub fn score_table(
+    all_guesses: &RowSet,
+    candidates: &RowSet,
+    show_progress: bool,
+) -> Result<Vec<Vec<usize>>> {
+    let def = candidates.def();
+    if def.variant().feedback != Feedback::BlackWhite {
+        // Working back from the answers relies on the score being the same both ways round, and
+        // on compatible rows being quick to list
+        return partition_table(all_guesses, candidates);
+    }
+    let possible_scores = def.possible_scores();
+
+    let mut scores = Vec::new();
+    scores.resize_with(def.capacity()?, || {
+        RwLock::new(vec![0usize; def.score_count()])
+    });
+
+    let total = candidates.count();
+    let step = total / 1000;
+    let progress = Mutex::new(0usize);
+    candidates.par_iter().for_each(|answer| {
+        for score in &possible_scores {
+            for guess in &def.compatible_with(&answer, *score) {
+                scores[guess.index()].write().unwrap()[score.index(def)] += 1;
+            }
+        }
+        if show_progress {
+            let mut progress = progress.lock().unwrap();
+            *progress += 1;
+            if (step == 0) || progress.is_multiple_of(step) {
+                print!("\r{:.1}%", (*progress as f32) * 100.0 / (total as f32));
+                io::stdout().flush().unwrap();
+            }
+        }
+    });
+
+    Ok(scores
+        .into_iter()
+        .map(|parts| parts.into_inner().unwrap())
+        .collect())
+}
+
+// How many of the candidates give each score for a guess, indexed by `Score::index`
+pub fn partition_counts(guess: &FatRow, candidates: &RowSet) -> Vec<usize> {
+    let def = candidates.def();
+    let mut parts = vec![0; def.score_count()];
+    for candidate in candidates {
+        parts[guess.score(&candidate).index(def)] += 1;
+    }
+    parts
+}
+
+// The same as `score_table`, by scoring every guess against every candidate, which is quicker
+// when there are few candidates
+pub fn partition_table(all_guesses: &RowSet, candidates: &RowSet) -> Result<Vec<Vec<usize>>> {
+    let mut table = vec![vec![]; candidates.def().capacity()?];
+    let parts: Vec<_> = all_guesses
+        .par_iter()
+        .map(|guess| (guess.index(), partition_counts(&guess, candidates)))
+        .collect();
+    for (index, parts) in parts {
+        table[index] = parts;
+    }
+    Ok(table)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recommendation {
+    pub rank: f64,
+    // Sorted
+    pub guesses: Vec<FatRow>,
+    // Whether the guesses could be the answer themselves
+    pub in_candidates: bool,
+}
+
+// The guesses that rank best, preferring ones that could be the answer
+pub fn recommend(
+    strategy: Strategy,
+    all_guesses: &RowSet,
+    candidates: &RowSet,
+    table: &[Vec<usize>],
+) -> Option<Recommendation> {
+    let guess_scores: Vec<_> = all_guesses
+        .par_iter()
+        .filter(|guess| table[guess.index()].iter().any(|&v| v > 0))
+        .map(|guess| (guess, strategy.rank(&table[guess.index()])))
+        .collect();
+
+    let rank = guess_scores
+        .iter()
+        .map(|(_, rank)| *rank)
+        .min_by(f64::total_cmp)?;
+
+    let (guesses_in, guesses_out): (Vec<_>, Vec<_>) = guess_scores
+        .into_iter()
+        .filter(|(_, v)| *v == rank)
+        .map(|(guess, _)| guess)
+        .partition(|guess| candidates.contains(guess));
+
+    let (mut guesses, in_candidates) = if !guesses_in.is_empty() {
+        (guesses_in, true)
+    } else {
+        (guesses_out, false)
+    };
+    guesses.sort_unstable();
+
+    Some(Recommendation {
+        rank,
+        guesses,
+        in_candidates,
+    })
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn test_rank() {
+    let parts = [0, 4, 1, 0, 3];
+    assert_eq!(Strategy::Average.rank(&parts), 8.0 / 3.0);
+    assert_eq!(Strategy::Minimax.rank(&parts), 4.0);
+    assert_eq!(Strategy::MostParts.rank(&parts), -3.0);
+    assert_eq!(Strategy::ExpectedSize.rank(&parts), 26.0 / 8.0);
+
+    let entropy = Strategy::Entropy.value(Strategy::Entropy.rank(&[2, 0, 2, 2, 2]));
+    assert!((entropy - 2.0).abs() < 1e-9);
+
+    // The order of the parts doesn't matter
+    for strategy in [Strategy::Entropy, Strategy::ExpectedSize] {
+        assert_eq!(strategy.rank(&[1, 2, 3, 7]), strategy.rank(&[7, 3, 1, 2]));
+    }
+}
+
+#[test]
+fn test_knuth_first_guess() -> Result<()> {
+    let def = RowDef::new(4, 6);
+    let mut all_guesses = RowSet::new(def)?;
+    all_guesses.insert_all();
+
+    let table = score_table(&all_guesses, &all_guesses, false)?;
+    let recommendation = recommend(Strategy::Minimax, &all_guesses, &all_guesses, &table).unwrap();
+    assert_eq!(recommendation.rank, 256.0);
+    assert!(recommendation.in_candidates);
+    assert_eq!(recommendation.guesses[0], def.row(&[0, 0, 1, 1]));
+
+    // Every candidate is counted once, under the score it gives
+    let guess = def.row(&[0, 0, 1, 1]);
+    assert_eq!(table[guess.index()].iter().sum::<usize>(), 1296);
+    assert_eq!(table[guess.index()][Score::new(4, 0).index(def)], 1);
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// A path in the temp directory that no other test, in this or another run, is using
+pub fn temp_path(name: &str) -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+        "mastermind-{}-{}-{}.txt",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.to_str().unwrap().to_owned()
+}
@@ -0,0 +1,314 @@
+use crate::rows::*;
+use crate::strategy::*;
+use anyhow::{anyhow, Context, Result};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, BufWriter};
+
+#[cfg(test)]
+mod test;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Objective {
+    // The fewest guesses on average, then the fewest in the worst case
+    #[default]
+    Expected,
+    // The fewest guesses in the worst case, then the fewest on average
+    WorstCase,
+}
+
+impl Objective {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "expected" | "average" => Ok(Objective::Expected),
+            "worst" | "worst-case" => Ok(Objective::WorstCase),
+            _ => Err(anyhow!("Unknown objective {}", name)),
+        }
+    }
+
+    fn key(&self, cost: Cost) -> (usize, usize) {
+        match self {
+            Objective::Expected => (cost.total, cost.max),
+            Objective::WorstCase => (cost.max, cost.total),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cost {
+    // Guesses summed over every secret
+    total: usize,
+    max: usize,
+}
+
+impl Cost {
+    // At best one secret is found by the next guess and the rest by the one after
+    fn lower_bound(count: usize) -> Self {
+        Self {
+            total: 2 * count - 1,
+            max: count.min(2),
+        }
+    }
+}
+
+// What to guess, and what to do next for each score other than all black
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    pub guess: FatRow,
+    pub children: BTreeMap<Score, Tree>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub secrets: usize,
+    pub average: f64,
+    pub max_depth: usize,
+    // How many secrets take each number of guesses, indexed by guesses
+    pub distribution: Vec<usize>,
+}
+
+impl Stats {
+    pub fn add(&mut self, guesses: usize) {
+        if self.distribution.len() <= guesses {
+            self.distribution.resize(guesses + 1, 0);
+        }
+        self.distribution[guesses] += 1;
+
+        self.average =
+            (self.average * self.secrets as f64 + guesses as f64) / (self.secrets + 1) as f64;
+        self.secrets += 1;
+        self.max_depth = self.max_depth.max(guesses);
+    }
+}
+
+impl Tree {
+    pub fn child(&self, score: Score) -> Option<&Tree> {
+        self.children.get(&score)
+    }
+
+    // The node the guesses and scores lead to, unless the guesses stray from the tree
+    pub fn follow(&self, history: &[(FatRow, Score)]) -> Option<&Tree> {
+        history.iter().try_fold(self, |node, (guess, score)| {
+            if node.guess == *guess {
+                node.child(*score)
+            } else {
+                None
+            }
+        })
+    }
+
+    // The guesses the tree takes to find the secret, if it does
+    pub fn play(&self, secret: &FatRow) -> Option<usize> {
+        let score = self.guess.score(secret);
+        if score == self.guess.def().winning_score() {
+            Some(1)
+        } else {
+            self.child(score)?.play(secret).map(|guesses| guesses + 1)
+        }
+    }
+
+    // Plays every secret, failing if the tree doesn't find one
+    pub fn stats(&self, secrets: &RowSet) -> Result<Stats> {
+        let mut stats = Stats::default();
+        for secret in secrets {
+            let guesses = self
+                .play(&secret)
+                .ok_or_else(|| anyhow!("Strategy tree doesn't find {}", secret))?;
+            stats.add(guesses);
+        }
+        Ok(stats)
+    }
+
+    // Saves as "HOLES COLOURS [VARIANT...]" followed by a line per guess: the scores leading to
+    // it, then the guess itself
+    pub fn save(&self, path: &str) -> Result<()> {
+        let mut f = BufWriter::new(File::create(path)?);
+        writeln!(f, "{}", self.guess.def().header())?;
+        self.write(&mut f, &mut vec![])?;
+        f.flush()?;
+        Ok(())
+    }
+
+    fn write(&self, f: &mut impl Write, path: &mut Vec<Score>) -> Result<()> {
+        let scores = path
+            .iter()
+            .map(|score| self.guess.def().format_score(*score))
+            .join(" ");
+        // Numbers, so the file doesn't depend on the naming
+        writeln!(f, "{}: {}", scores, self.guess.with_naming(Naming::Numbers))?;
+
+        for (score, child) in &self.children {
+            path.push(*score);
+            child.write(f, path)?;
+            path.pop();
+        }
+        Ok(())
+    }
+
+    pub fn load(path: &str, naming: Naming) -> Result<Self> {
+        let f = BufReader::new(File::open(path)?);
+        let mut lines = f.lines();
+
+        let header = lines.next().context("Empty strategy tree file")??;
+        let def = RowDef::parse_header(&header, naming).context("Bad strategy tree")?;
+
+        let mut guesses = BTreeMap::new();
+        for line in lines {
+            let line = line?;
+            let bad_line = || anyhow!("Bad strategy tree line: {}", line);
+
+            let (scores, guess) = line.split_once(':').ok_or_else(bad_line)?;
+            let scores = scores
+                .split_whitespace()
+                .map(|score| def.parse_score(score).map_err(|_| bad_line()))
+                .collect::<Result<Vec<_>>>()?;
+            let guess = def.parse_row(guess).map_err(|_| bad_line())?;
+            guesses.insert(scores, guess);
+        }
+
+        Self::from_guesses(&guesses, &mut vec![]).context("Strategy tree has no root")
+    }
+
+    fn from_guesses(guesses: &BTreeMap<Vec<Score>, FatRow>, path: &mut Vec<Score>) -> Option<Self> {
+        let guess = *guesses.get(path)?;
+        let mut children = BTreeMap::new();
+        for score in guess.def().possible_scores() {
+            path.push(score);
+            if let Some(child) = Self::from_guesses(guesses, path) {
+                children.insert(score, child);
+            }
+            path.pop();
+        }
+        Some(Self { guess, children })
+    }
+}
+
+// Searches for a tree that is best by the objective, trying the `width` guesses the strategy ranks
+// best at each step, and abandoning a guess as soon as it can't beat the best found so far. Only a
+// width of usize::MAX is exhaustive; narrower is a beam search that relies on the strategy's
+// ranking, and a width of 1 is greedy, leaving the objective nothing to choose between.
+pub struct Search<'a> {
+    pub all_guesses: &'a RowSet,
+    pub strategy: Strategy,
+    pub objective: Objective,
+    pub width: usize,
+}
+
+impl<'a> Search<'a> {
+    pub fn build(&self, candidates: &RowSet) -> Option<Tree> {
+        self.search(candidates).map(|(tree, _)| tree)
+    }
+
+    fn search(&self, candidates: &RowSet) -> Option<(Tree, Cost)> {
+        let count = candidates.count();
+        if count <= 2 {
+            // Guessing either of two candidates is as good as it gets
+            let mut iter = candidates.iter();
+            let guess = iter.next()?;
+            let mut children = BTreeMap::new();
+            if let Some(other) = iter.next() {
+                children.insert(
+                    guess.score(&other),
+                    Tree {
+                        guess: other,
+                        children: BTreeMap::new(),
+                    },
+                );
+            }
+            let cost = Cost {
+                total: 2 * count - 1,
+                max: count,
+            };
+            return Some((Tree { guess, children }, cost));
+        }
+
+        let mut best: Option<(Tree, Cost)> = None;
+        'guesses: for guess in self.ranked_guesses(candidates) {
+            let parts = partition(&guess, candidates);
+
+            let mut cost = Cost {
+                total: count,
+                max: 1,
+            };
+            let mut children = BTreeMap::new();
+            for (i, (score, part)) in parts.iter().enumerate() {
+                if let Some((_, best_cost)) = &best {
+                    // Assume the parts still to come are solved as fast as possible
+                    let bound = parts[i..].iter().fold(cost, |cost, (_, part)| {
+                        let lower = Cost::lower_bound(part.count());
+                        Cost {
+                            total: cost.total + lower.total,
+                            max: cost.max.max(lower.max + 1),
+                        }
+                    });
+                    if self.objective.key(bound) >= self.objective.key(*best_cost) {
+                        continue 'guesses;
+                    }
+                }
+
+                let Some((child, child_cost)) = self.search(part) else {
+                    continue 'guesses;
+                };
+                cost.total += child_cost.total;
+                cost.max = cost.max.max(child_cost.max + 1);
+                children.insert(*score, child);
+            }
+
+            if best
+                .as_ref()
+                .is_none_or(|best| self.objective.key(cost) < self.objective.key(best.1))
+            {
+                best = Some((Tree { guess, children }, cost));
+            }
+        }
+
+        best
+    }
+
+    // The guesses worth trying, best first, leaving out any that can't tell the candidates apart
+    fn ranked_guesses(&self, candidates: &RowSet) -> Vec<FatRow> {
+        let count = candidates.count();
+        let mut guesses: Vec<_> = self
+            .all_guesses
+            .par_iter()
+            .filter_map(|guess| {
+                let parts = partition_counts(&guess, candidates);
+                let useful = candidates.contains(&guess) || parts.iter().all(|&v| v < count);
+                useful.then(|| {
+                    (
+                        self.strategy.rank(&parts),
+                        !candidates.contains(&guess),
+                        guess,
+                    )
+                })
+            })
+            .collect();
+        guesses.sort_unstable_by(|(a_rank, a_out, a), (b_rank, b_out, b)| {
+            a_rank.total_cmp(b_rank).then((a_out, a).cmp(&(b_out, b)))
+        });
+        guesses
+            .into_iter()
+            .take(self.width.max(1))
+            .map(|(_, _, guess)| guess)
+            .collect()
+    }
+}
+
+// The candidates that would give each score other than all black, in score order
+fn partition(guess: &FatRow, candidates: &RowSet) -> Vec<(Score, RowSet)> {
+    let winning_score = guess.def().winning_score();
+    let mut parts: BTreeMap<Score, RowSet> = BTreeMap::new();
+    for candidate in candidates {
+        let score = guess.score(&candidate);
+        if score != winning_score {
+            parts
+                .entry(score)
+                .or_insert_with(|| candidates.cleared())
+                .insert(&candidate);
+        }
+    }
+    parts.into_iter().collect()
+}
@@ -0,0 +1,91 @@
+use super::*;
+use crate::testutil::temp_path;
+
+fn all_rows(def: RowDef) -> RowSet {
+    let mut set = RowSet::new(def).unwrap();
+    set.insert_all();
+    set
+}
+
+#[test]
+fn test_build() -> Result<()> {
+    let all_guesses = all_rows(RowDef::new(3, 4));
+    let search = |objective, width| Search {
+        all_guesses: &all_guesses,
+        strategy: Strategy::Minimax,
+        objective,
+        width,
+    };
+
+    let greedy = search(Objective::Expected, 1).build(&all_guesses).unwrap();
+    let greedy = greedy.stats(&all_guesses)?;
+    assert_eq!(greedy.secrets, 64);
+    assert_eq!(greedy.distribution.iter().sum::<usize>(), 64);
+
+    // Trying more guesses can only help
+    let wider = search(Objective::Expected, 4).build(&all_guesses).unwrap();
+    let wider = wider.stats(&all_guesses)?;
+    assert!(wider.average <= greedy.average);
+
+    let worst = search(Objective::WorstCase, 4).build(&all_guesses).unwrap();
+    let worst = worst.stats(&all_guesses)?;
+    assert!(worst.max_depth <= greedy.max_depth);
+
+    Ok(())
+}
+
+#[test]
+fn test_save_load() -> Result<()> {
+    let def = RowDef::new(3, 3);
+    let all_guesses = all_rows(def);
+    let tree = Search {
+        all_guesses: &all_guesses,
+        strategy: Strategy::Entropy,
+        objective: Objective::Expected,
+        width: 2,
+    }
+    .build(&all_guesses)
+    .unwrap();
+
+    let path = &temp_path("tree");
+    tree.save(path)?;
+    let loaded = Tree::load(path, Naming::Numbers);
+    std::fs::remove_file(path)?;
+
+    assert_eq!(loaded?, tree);
+    assert!(tree.play(&def.row(&[2, 1, 0])).is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_objectives() -> Result<()> {
+    let def = RowDef::with_variant(4, 2, Variant::parse(&["total-only"])?);
+    let all_guesses = all_rows(def);
+    let search = |objective, width| Search {
+        all_guesses: &all_guesses,
+        strategy: Strategy::Minimax,
+        objective,
+        width,
+    };
+
+    let expected = search(Objective::Expected, usize::MAX)
+        .build(&all_guesses)
+        .unwrap();
+    let expected = expected.stats(&all_guesses)?;
+    assert_eq!((expected.average, expected.max_depth), (3.5, 7));
+
+    let worst = search(Objective::WorstCase, usize::MAX)
+        .build(&all_guesses)
+        .unwrap();
+    let worst = worst.stats(&all_guesses)?;
+    assert_eq!((worst.average, worst.max_depth), (3.625, 6));
+
+    // Trying a single guess at each step leaves nothing for the objective to choose between
+    assert_eq!(
+        search(Objective::Expected, 1).build(&all_guesses),
+        search(Objective::WorstCase, 1).build(&all_guesses)
+    );
+
+    Ok(())
+}